@@ -1,6 +1,8 @@
 use crate::Board;
 use crate::Field;
+use crate::House;
 
+#[allow(clippy::enum_variant_names)]
 pub enum IterType {
     LineIter,
     ColIter,
@@ -15,8 +17,7 @@ pub struct BoardIter<'a> {
     col: usize,
     idx: usize,
     quadrant_idx: usize,
-    start_line: usize,
-    start_col: usize,
+    cells: Vec<usize>,  // fields of the quadrant or region in iteration order
 }
 
 impl<'a> Iterator for BoardIter<'a> {
@@ -35,20 +36,21 @@ impl<'a> Iterator for BoardIter<'a> {
 
 impl<'a> BoardIter<'a> {
     pub fn new_line_iter(b: &'a Board, line: usize) -> Self {
-        BoardIter { itertype: IterType::LineIter, b, line, col: 0, idx: 0, quadrant_idx: 0, start_line: 0, start_col: 0}
+        BoardIter { itertype: IterType::LineIter, b, line, col: 0, idx: 0, quadrant_idx: 0, cells: Vec::new()}
     }
 
     pub fn new_col_iter(b: &'a Board, col: usize) -> Self {
-        BoardIter { itertype: IterType::ColIter, b, line: 0, col, idx: 0, quadrant_idx: 0, start_line: 0, start_col: 0}
+        BoardIter { itertype: IterType::ColIter, b, line: 0, col, idx: 0, quadrant_idx: 0, cells: Vec::new()}
     }
 
     pub fn new_quad_iter(b: &'a Board, line: usize, col: usize) -> Self {
-        let (line, col) = b.quadrant_start_from_line_col(line, col);
-        BoardIter { itertype: IterType::QuadIter, b, line, col, idx: 0, quadrant_idx: 0, start_line: line, start_col: col}
+        let cells = if b.check_valid_coord(line, col) { b.house_cells(House::Quad(b.quad_of(line, col))) } else { Vec::new() };
+        let (line, col) = cells.first().map_or((b.line_size, b.line_size), |&idx| b.line_col_from_idx(idx));
+        BoardIter { itertype: IterType::QuadIter, b, line, col, idx: 0, quadrant_idx: 0, cells}
     }
 
     pub fn new_field_iter(b: &'a Board) -> Self {
-        BoardIter { itertype: IterType::FieldIter, b, line: 0, col: 0, idx: 0, quadrant_idx: 0, start_line: 0, start_col: 0}
+        BoardIter { itertype: IterType::FieldIter, b, line: 0, col: 0, idx: 0, quadrant_idx: 0, cells: Vec::new()}
     }
 
     fn advance_idx(&mut self) {
@@ -57,15 +59,10 @@ impl<'a> BoardIter<'a> {
             IterType::ColIter  => self.line += 1,
             IterType::QuadIter => {
                 self.quadrant_idx += 1;
-                if self.quadrant_idx < self.b.line_size {
-                    let line_adv = self.quadrant_idx / self.b.base;
-                    let col_adv = self.quadrant_idx - line_adv * self.b.base;
-                    self.line = self.start_line + line_adv;
-                    self.col = self.start_col + col_adv;
-                } else {
-                    self.line = self.b.line_size;
-                    self.col = self.b.line_size;
-                }
+                (self.line, self.col) = match self.cells.get(self.quadrant_idx) {
+                    Some(&idx) => self.b.line_col_from_idx(idx),
+                    None => (self.b.line_size, self.b.line_size),
+                };
             },
             IterType::FieldIter => {
                 (self.line, self.col) = self.b.line_col_from_idx(self.idx + 1);
@@ -0,0 +1,95 @@
+// Set of possible numbers of a field as a bitmask of up to 128 numbers, enough for every supported line size.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Candidates(u128);
+
+pub struct CandidatesIter {
+    bits: u128,
+}
+
+impl Iterator for CandidatesIter {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        if self.bits == 0 {
+            None
+        } else {
+            let val = self.bits.trailing_zeros() as usize;
+            self.bits &= self.bits - 1;
+            Some(val)
+        }
+    }
+}
+
+impl FromIterator<usize> for Candidates {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut c = Candidates::empty();
+        for val in iter {
+            c.insert(val);
+        }
+        c
+    }
+}
+
+impl Candidates {
+    pub const CAPACITY: usize = 128;
+
+    pub fn empty() -> Self {
+        Candidates(0)
+    }
+
+    pub fn full(count: usize) -> Self {  // numbers 0..count
+        if count >= Self::CAPACITY {
+            Candidates(u128::MAX)
+        } else {
+            Candidates((1u128 << count) - 1)
+        }
+    }
+
+    pub fn single(val: usize) -> Self {
+        debug_assert!(val < Self::CAPACITY);
+        Candidates(1u128 << val)
+    }
+
+    pub fn contains(&self, val: usize) -> bool {
+        val < Self::CAPACITY && (self.0 >> val) & 1 == 1
+    }
+
+    pub fn insert(&mut self, val: usize) {
+        debug_assert!(val < Self::CAPACITY);
+        self.0 |= 1u128 << val;
+    }
+
+    pub fn remove(&mut self, val: usize) {
+        if val < Self::CAPACITY {
+            self.0 &= !(1u128 << val);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    pub fn union(&self, other: Candidates) -> Self {
+        Candidates(self.0 | other.0)
+    }
+
+    pub fn intersection(&self, other: Candidates) -> Self {
+        Candidates(self.0 & other.0)
+    }
+
+    pub fn difference(&self, other: Candidates) -> Self {
+        Candidates(self.0 & !other.0)
+    }
+
+    pub fn iter(&self) -> CandidatesIter {
+        CandidatesIter { bits: self.0 }
+    }
+}
@@ -0,0 +1,291 @@
+use std::cmp::Reverse;
+
+use crate::Board;
+use crate::Cage;
+use crate::Candidates;
+use crate::Field;
+use crate::solutions;
+
+// Exact cover matrix in Knuth's Dancing Links representation.
+// Node 0 is the root, nodes 1..=columns are the column headers, the rest are matrix entries.
+struct Dlx {
+    left: Vec<usize>,
+    right: Vec<usize>,
+    up: Vec<usize>,
+    down: Vec<usize>,
+    column: Vec<usize>,
+    row: Vec<usize>,
+    size: Vec<usize>,
+    restart: usize,  // earlier searches given up, ties between columns are broken differently in every one
+    budget: Option<usize>,  // branches left before the search gives up, unlimited if None
+    exhausted: bool,  // a branch was refused because the budget ran out
+}
+
+impl Dlx {
+    fn new(columns: usize) -> Self {
+        let headers = columns + 1;
+        Dlx {
+            left: (0..headers).map(|i| if i == 0 { columns } else { i - 1 }).collect(),
+            right: (0..headers).map(|i| (i + 1) % headers).collect(),
+            up: (0..headers).collect(),
+            down: (0..headers).collect(),
+            column: (0..headers).collect(),
+            row: vec!(usize::MAX; headers),
+            size: vec!(0; headers),
+            restart: 0,
+            budget: None,
+            exhausted: false,
+        }
+    }
+
+    fn add_row(&mut self, row: usize, columns: &[usize]) -> usize {  // the first node of the row
+        let first = self.left.len();
+        for (k, &c) in columns.iter().enumerate() {
+            let header = c + 1;
+            let node = self.left.len();
+
+            // vertical list of the column
+            let last = self.up[header];
+            self.up.push(last);
+            self.down.push(header);
+            self.down[last] = node;
+            self.up[header] = node;
+            self.size[header] += 1;
+
+            // horizontal list of the row
+            self.left.push(if k == 0 { node } else { node - 1 });
+            self.right.push(first);
+            if k > 0 {
+                self.right[node - 1] = node;
+            }
+            self.left[first] = node;
+
+            self.column.push(header);
+            self.row.push(row);
+        }
+        first
+    }
+
+    fn removed(&self, r: usize) -> usize {  // rows that taking the row of node r would remove
+        let mut removed = 0;
+        let mut j = self.right[r];
+        while j != r {
+            removed += self.size[self.column[j]] - 1;
+            j = self.right[j];
+        }
+        removed
+    }
+
+    fn select(&mut self, r: usize, partial: &mut Vec<usize>) {  // take the row of node r into the cover for good
+        partial.push(self.row[r]);
+        let mut j = r;
+        loop {
+            self.cover(self.column[j]);
+            j = self.right[j];
+            if j == r {
+                break;
+            }
+        }
+    }
+
+    fn select_forced(&mut self, partial: &mut Vec<usize>) -> bool {  // select the rows of columns left with a single row, false if a column has none
+        loop {
+            let mut c = self.right[0];
+            while c != 0 && self.size[c] > 1 {
+                c = self.right[c];
+            }
+            match c {
+                0 => return true,
+                _ if self.size[c] == 0 => return false,
+                _ => self.select(self.down[c], partial),
+            }
+        }
+    }
+
+    fn cover(&mut self, c: usize) {
+        self.right[self.left[c]] = self.right[c];
+        self.left[self.right[c]] = self.left[c];
+        let mut i = self.down[c];
+        while i != c {
+            let mut j = self.right[i];
+            while j != i {
+                self.up[self.down[j]] = self.up[j];
+                self.down[self.up[j]] = self.down[j];
+                self.size[self.column[j]] -= 1;
+                j = self.right[j];
+            }
+            i = self.down[i];
+        }
+    }
+
+    fn uncover(&mut self, c: usize) {
+        let mut i = self.up[c];
+        while i != c {
+            let mut j = self.left[i];
+            while j != i {
+                self.size[self.column[j]] += 1;
+                self.up[self.down[j]] = j;
+                self.down[self.up[j]] = j;
+                j = self.left[j];
+            }
+            i = self.up[i];
+        }
+        self.right[self.left[c]] = c;
+        self.left[self.right[c]] = c;
+    }
+
+    fn search(&mut self, partial: &mut Vec<usize>, covers: &mut Vec<Vec<usize>>, limit: usize) -> bool {  // algorithm X, collects exact covers until limit is reached or the budget runs out
+        if self.right[0] == 0 {
+            covers.push(partial.clone());
+            return covers.len() >= limit;
+        }
+
+        // column with the fewest remaining rows, on a tie the cell columns that come first are taken last
+        let mut c = self.right[0];
+        let mut j = self.right[c];
+        while j != 0 {
+            if (self.size[j], Reverse(solutions::scramble(j, self.restart))) < (self.size[c], Reverse(solutions::scramble(c, self.restart))) {
+                c = j;
+            }
+            j = self.right[j];
+        }
+        if self.size[c] == 0 {
+            return false;
+        }
+        if self.size[c] > 1 {
+            match self.budget.as_mut() {
+                Some(0) => {
+                    self.exhausted = true;
+                    return true;
+                },
+                Some(budget) => *budget -= 1,
+                None => (),
+            }
+        }
+
+        // rows removing the fewest others first, ties in the order of the restart
+        let mut rows = Vec::new();
+        let mut r = self.down[c];
+        while r != c {
+            rows.push(r);
+            r = self.down[r];
+        }
+        if rows.len() > 1 {
+            rows.sort_by_key(|&r| (self.removed(r), solutions::scramble(self.row[r], self.restart)));
+        }
+
+        self.cover(c);
+        for r in rows {
+            partial.push(self.row[r]);
+            let mut j = self.right[r];
+            while j != r {
+                self.cover(self.column[j]);
+                j = self.right[j];
+            }
+
+            if self.search(partial, covers, limit) {
+                return true;
+            }
+
+            let mut j = self.left[r];
+            while j != r {
+                self.uncover(self.column[j]);
+                j = self.left[j];
+            }
+            partial.pop();
+        }
+        self.uncover(c);
+        false
+    }
+}
+
+pub fn solve(b: Board) -> Option<Board> {  // a search lost in a hopeless branch starts over with other columns and a larger budget
+    let mut budget = solutions::RESTART_BUDGET;
+    let mut restart = 0;
+    loop {
+        let (mut found, exhausted) = exact_covers(&b, 1, restart, Some(budget));
+        if !exhausted || !found.is_empty() {
+            return found.pop();
+        }
+        budget += budget / 2;
+        restart += 1;
+    }
+}
+
+pub fn solutions(b: Board, limit: usize) -> Vec<Board> {  // up to limit solutions in search order
+    exact_covers(&b, limit, 0, None).0
+}
+
+fn exact_covers(b: &Board, limit: usize, restart: usize, budget: Option<usize>) -> (Vec<Board>, bool) {  // solutions and whether the budget ran out before limit was reached
+    if b.content.is_empty() {
+        return (Vec::new(), false);
+    }
+
+    // columns: one per cell, one per house and value, one per cage and one per cage and value
+    let houses = b.houses();
+    let cells = b.content.len();
+    let mut cell_houses: Vec<Vec<usize>> = vec!(Vec::new(); cells);
+    for (h, &house) in houses.iter().enumerate() {
+        for idx in b.house_cells(house) {
+            cell_houses[idx].push(h);
+        }
+    }
+    let cage_columns = cells + houses.len() * b.line_size;
+    let value_columns = cage_columns + b.cages.len();
+
+    // rows: one per cell and possible value
+    let mut dlx = Dlx::new(value_columns + b.cages.len() * b.line_size);
+    dlx.restart = restart;
+    dlx.budget = budget;
+    let mut rows: Vec<Option<(usize, usize)>> = Vec::new();
+    let mut givens = Vec::new();
+    for (idx, field) in b.content.iter().enumerate() {
+        let values = match field {
+            Field::Number(n) => Candidates::single(*n),
+            Field::OptionList(list) => *list,
+        };
+        for val in values.iter() {
+            let mut columns = vec!(idx);
+            columns.extend(cell_houses[idx].iter().map(|h| cells + h * b.line_size + val));
+            columns.extend(b.cage_of(idx).map(|c| value_columns + c * b.line_size + val));
+            let node = dlx.add_row(rows.len(), &columns);
+            if let Field::Number(_) = field {
+                givens.push(node);
+            }
+            rows.push(Some((idx, val)));
+        }
+    }
+
+    // and one per cage and sum combination, covering the values it leaves out, so every value of
+    // the cage is either taken by exactly one of its cells or left out by the chosen combination
+    for (c, cage) in b.cages.iter().enumerate() {
+        for combo in Cage::combinations(b.line_size, cage.cells.len(), cage.sum) {
+            let mut columns = vec!(cage_columns + c);
+            columns.extend((0..b.line_size).filter(|&val| !combo.contains(val)).map(|val| value_columns + c * b.line_size + val));
+            dlx.add_row(rows.len(), &columns);
+            rows.push(None);
+        }
+    }
+
+    // the numbers already set and the rows they force are part of every cover, taking them
+    // before the search keeps it from branching on columns that have a single row anyway
+    let mut partial = Vec::new();
+    for node in givens {
+        dlx.select(node, &mut partial);
+    }
+    let mut covers = Vec::new();
+    if limit > 0 && dlx.select_forced(&mut partial) {
+        dlx.search(&mut partial, &mut covers, limit);
+    }
+
+    let solutions = covers.into_iter().filter_map(|cover| {
+        let mut solution = b.clone();
+        for (idx, val) in cover.into_iter().filter_map(|row| rows[row]) {
+            if solution.get_num_index(idx).is_none() && !solution.set_num_index(idx, val) {
+                return None;
+            }
+        }
+        Some(solution)
+    }).collect();
+    (solutions, dlx.exhausted)
+}
@@ -0,0 +1,102 @@
+// Text encodings of the numbers of a board in the one-line format.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Encoding {
+    Numbers,    // single digits up to 9x9, zero padded two digit numbers above
+    ZeroBased,  // one character per field: 0-9A-Z, e.g. 0-9A-F for 16x16
+    OneBased,   // one character per field: 1-9A-Z, e.g. 1-9A-G for 16x16
+    Letters,    // one character per field: A-Z, e.g. A-Y for 25x25
+}
+
+const SYMBOLS: &str = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+impl Encoding {
+    pub const ALL: [Encoding; 4] = [
+        Encoding::Numbers,
+        Encoding::ZeroBased,
+        Encoding::OneBased,
+        Encoding::Letters,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Encoding::Numbers   => "numbers",
+            Encoding::ZeroBased => "zero-based",
+            Encoding::OneBased  => "one-based",
+            Encoding::Letters   => "letters",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Encoding::ALL.into_iter().find(|e| e.name().eq_ignore_ascii_case(name))
+    }
+
+    fn symbols(&self) -> &'static str {
+        match self {
+            Encoding::Numbers   => &SYMBOLS[1..10],
+            Encoding::ZeroBased => SYMBOLS,
+            Encoding::OneBased  => &SYMBOLS[1..],
+            Encoding::Letters   => &SYMBOLS[10..],
+        }
+    }
+
+    pub fn supports(&self, line_size: usize) -> bool {
+        match self {
+            Encoding::Numbers => line_size <= 99,
+            _ => line_size <= self.symbols().len(),
+        }
+    }
+
+    pub fn width(&self, line_size: usize) -> usize {  // characters per field
+        match self {
+            Encoding::Numbers if line_size > 9 => 2,
+            _ => 1,
+        }
+    }
+
+    pub fn encode(&self, num: usize, line_size: usize) -> String {  // zero-based number to its symbol
+        match self {
+            Encoding::Numbers => format!("{0:01$}", num + 1, self.width(line_size)),
+            _ => self.symbols()[num..(num + 1)].to_string(),
+        }
+    }
+
+    pub fn decode(&self, token: &str) -> Option<usize> {  // the value as written, counting from one
+        match self {
+            Encoding::Numbers => {
+                // only plain digits, parse would also accept a sign
+                if token.is_empty() || !token.chars().all(|c| c.is_ascii_digit()) {
+                    return None;
+                }
+                token.parse::<usize>().ok()
+            },
+            _ => {
+                let mut chars = token.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => self.symbols().find(c.to_ascii_uppercase()).map(|i| i + 1),
+                    _ => None,
+                }
+            },
+        }
+    }
+
+    pub fn detect(symbols: &[char], line_size: usize) -> Option<Self> {  // encoding of a board with one character per field, None if unsupported or ambiguous
+        let has = |s: char| symbols.iter().any(|c| c.to_ascii_uppercase() == s);
+        // letters are only used above 9x9, on smaller boards they are reported as invalid tokens
+        let encoding = if line_size <= 9 {
+            Encoding::Numbers
+        } else if has('0') || !Encoding::OneBased.supports(line_size) {
+            Encoding::ZeroBased
+        } else if !symbols.iter().any(|c| c.is_ascii_digit()) {
+            Encoding::Letters
+        } else if Encoding::OneBased.symbols().chars().nth(line_size - 1).is_some_and(has) {
+            Encoding::OneBased
+        } else {
+            return None;
+        };
+        if encoding.supports(line_size) { Some(encoding) } else { None }
+    }
+
+    pub fn is_ambiguous(symbols: &[char], line_size: usize) -> bool {  // digits without 0 and without the highest one-based symbol read as zero-based and one-based numbers alike
+        line_size > 9 && Encoding::OneBased.supports(line_size) && symbols.iter().any(|c| c.is_ascii_digit()) && Encoding::detect(symbols, line_size).is_none()
+    }
+}
@@ -0,0 +1,62 @@
+use std::fmt;
+
+// Positions count characters of the input. Positions and cells are zero-based, the messages count from one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    WrongLength { length: usize, valid: Vec<usize> },
+    InvalidToken { position: usize, token: String },
+    ValueOutOfRange { position: usize, value: usize, max: usize },
+    ConflictingGivens { cell: (usize, usize), peer: (usize, usize), value: usize },
+    WrongRowLength { line: usize, length: usize, valid: Vec<usize> },
+    WrongRowCount { rows: usize, expected: usize },
+    UnsupportedBase { base: usize },
+    WrongRegionSize { region: usize, size: usize, expected: usize },
+    InvalidCage { cage: usize, size: usize, sum: usize },
+    OverlappingCages { cell: (usize, usize) },
+    AmbiguousEncoding { line_size: usize },
+    WrongFieldCount { list: &'static str, length: usize, expected: usize },
+    GivenAndValue { position: usize },
+    NotDerived { position: usize },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::WrongLength { length, valid } =>
+                write!(f, "input has {} characters, expected one of {}", length, join(valid)),
+            ParseError::InvalidToken { position, token } =>
+                write!(f, "invalid token '{}' at position {}", token, position + 1),
+            ParseError::ValueOutOfRange { position, value, max } =>
+                write!(f, "value {} at position {} is out of range 1-{}", value, position + 1, max),
+            ParseError::ConflictingGivens { cell, peer, value } =>
+                write!(f, "value {} at r{}c{} conflicts with r{}c{}", value, cell.0 + 1, cell.1 + 1, peer.0 + 1, peer.1 + 1),
+            ParseError::WrongRowLength { line, length, valid } =>
+                write!(f, "row in line {} has {} fields, expected {}", line + 1, length, join(valid)),
+            ParseError::WrongRowCount { rows, expected } =>
+                write!(f, "grid has {} rows, expected {}", rows, expected),
+            ParseError::UnsupportedBase { base } =>
+                write!(f, "base {} is not supported, expected 2-9", base),
+            ParseError::WrongRegionSize { region, size, expected } =>
+                write!(f, "region {} has {} fields, expected {}", region + 1, size, expected),
+            ParseError::InvalidCage { cage, size, sum } =>
+                write!(f, "cage {} of {} fields can't add up to {}", cage + 1, size, sum),
+            ParseError::OverlappingCages { cell } =>
+                write!(f, "field r{}c{} belongs to more than one cage", cell.0 + 1, cell.1 + 1),
+            ParseError::AmbiguousEncoding { line_size } =>
+                write!(f, "symbols of the {0}x{0} board read as zero-based and as one-based numbers, choose an encoding", line_size),
+            ParseError::WrongFieldCount { list, length, expected } =>
+                write!(f, "{} has {} fields, expected {}", list, length, expected),
+            ParseError::GivenAndValue { position } =>
+                write!(f, "field {} has both a given and a value", position + 1),
+            ParseError::NotDerived { position } =>
+                write!(f, "entered field {} holds no value", position + 1),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn join(values: &[usize]) -> String {
+    let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    values.join(", ")
+}
@@ -0,0 +1,395 @@
+use crate::Board;
+use crate::Cage;
+use crate::Difficulty;
+use crate::Encoding;
+use crate::Field;
+use crate::ParseError;
+use crate::Shape;
+use crate::grid;
+
+// File formats used by other solvers to exchange puzzles:
+// SadMan Sudoku (.sdk): metadata header lines "#<code><value>", then one row per line, '.' for empty fields
+// .sdm: one puzzle per line in the one-line format, '0' for empty fields, ".." for those of two digit numbers
+// Simple Sudoku (.ss): rows with '|' between boxes, '-' separator lines and an optional '*' frame
+// .jigsaw: headers as in .sdk, the region layout with one symbol per field (letters, or numbers separated by
+// spaces above 26 regions), then the givens as in .sdk
+// .killer: headers as in .sdk, the cage layout with one symbol per field (letters, or numbers separated by spaces
+// above 52 cages, '.' outside of cages), the cage sums as "<symbol>=<sum>", then the givens as in .sdk if there are any
+// Boards larger than 9x9 are written with the one-based single character encoding, or two digit numbers
+// if they are too large for it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+    Sdk,
+    Sdm,
+    Ss,
+    Jigsaw,
+    Killer,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Metadata {
+    entries: Vec<(char, String)>,  // header codes and values in file order, unknown codes are kept as well
+}
+
+impl Format {
+    pub const ALL: [Format; 5] = [
+        Format::Sdk,
+        Format::Sdm,
+        Format::Ss,
+        Format::Jigsaw,
+        Format::Killer,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Sdk => "sdk",
+            Format::Sdm => "sdm",
+            Format::Ss  => "ss",
+            Format::Jigsaw => "jigsaw",
+            Format::Killer => "killer",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {  // also accepts file names and extensions like "puzzle.sdk" or ".ss"
+        let name = name.rsplit('.').next().unwrap_or(name);
+        Format::ALL.into_iter().find(|f| f.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn puzzles<'a>(&self, input: &'a str) -> Vec<&'a str> {  // .sdm files hold one puzzle per line, the others one per file
+        match self {
+            Format::Sdm => input.lines().map(|l| l.trim()).filter(|l| !l.is_empty() && !l.starts_with('#')).collect(),
+            _ => vec!(input),
+        }
+    }
+}
+
+impl Metadata {
+    pub const AUTHOR: char = 'A';
+    pub const DESCRIPTION: char = 'D';
+    pub const COMMENT: char = 'C';
+    pub const DATE: char = 'B';
+    pub const SOURCE: char = 'S';
+    pub const LEVEL: char = 'L';
+    pub const URL: char = 'U';
+
+    pub fn new() -> Self {
+        Metadata { entries: Vec::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn entries(&self) -> &[(char, String)] {
+        &self.entries
+    }
+
+    pub fn get(&self, code: char) -> Option<&str> {  // first value of code, codes like comments may repeat
+        self.entries.iter().find(|(c, _)| *c == code).map(|(_, v)| v.as_str())
+    }
+
+    pub fn set(&mut self, code: char, value: &str) {  // replaces the first value of code or appends it
+        match self.entries.iter_mut().find(|(c, _)| *c == code) {
+            Some(entry) => entry.1 = value.to_string(),
+            None => self.entries.push((code, value.to_string())),
+        }
+    }
+
+    pub fn push(&mut self, code: char, value: &str) {
+        self.entries.push((code, value.to_string()));
+    }
+
+    pub fn author(&self) -> Option<&str> {
+        self.get(Metadata::AUTHOR)
+    }
+
+    pub fn source(&self) -> Option<&str> {
+        self.get(Metadata::SOURCE)
+    }
+
+    pub fn difficulty(&self) -> Option<Difficulty> {
+        self.get(Metadata::LEVEL).and_then(Difficulty::from_name)
+    }
+}
+
+fn encoding(b: &Board) -> Encoding {
+    if Encoding::Numbers.width(b.line_size) == 1 { Encoding::Numbers } else { Encoding::OneBased }
+}
+
+fn region_symbol(region: usize, line_size: usize) -> String {
+    if line_size <= 26 { char::from(b'A' + region as u8).to_string() } else { (region + 1).to_string() }
+}
+
+fn cage_symbol(cage: usize, cages: usize) -> String {
+    match cage {
+        0..=25 if cages <= 52 => char::from(b'A' + cage as u8).to_string(),
+        26..=51 if cages <= 52 => char::from(b'a' + (cage - 26) as u8).to_string(),
+        _ => (cage + 1).to_string(),
+    }
+}
+
+fn rows(b: &Board) -> Vec<String> {
+    let line: Vec<char> = b.print_with(false, encoding(b)).trim_end().chars().collect();
+    if line.is_empty() {
+        return Vec::new();
+    }
+    line.chunks(line.len() / b.line_size).map(|r| r.iter().collect()).collect()
+}
+
+pub fn read_format(b: &mut Board, input: &str, format: Format) -> Result<Metadata, ParseError> {
+    let mut metadata = Metadata::new();
+    match format {
+        Format::Sdk => {
+            // headers, then the grid, optionally in a [Puzzle] section, later sections hold solver state and are skipped
+            let mut grid = String::new();
+            let mut section = None;
+            for line in input.lines() {
+                // other lines are kept as empty lines to report the right line numbers
+                let trimmed = line.trim();
+                if trimmed.starts_with('[') {
+                    section = Some(trimmed.eq_ignore_ascii_case("[Puzzle]"));
+                } else if section == Some(false) {
+                } else if let Some(header) = trimmed.strip_prefix('#') {
+                    let mut chars = header.chars();
+                    if let Some(code) = chars.next() {
+                        metadata.push(code, chars.as_str().trim());
+                    }
+                } else {
+                    grid.push_str(line);
+                }
+                grid.push('\n');
+            }
+            grid::read_grid(b, &grid, Some(Encoding::OneBased), None)?;
+        },
+        Format::Sdm => {
+            // '0' marks empty fields of single character encodings, two digit numbers never have a square as length
+            let line = input.trim();
+            let len = line.chars().count();
+            let line_size = grid::LINE_SIZES.into_iter().find(|l| l * l == len);
+            if line_size.is_some_and(|l| l <= 9) {
+                b.read(&line.replace('0', "."))?;
+            } else if line_size.is_some() {
+                b.read_with(&line.replace('0', "."), Encoding::OneBased)?;
+            } else {
+                b.read(line)?;
+            }
+        },
+        Format::Ss => {
+            // the frame lines start with '*', keep them as empty lines to report the right line numbers
+            let grid: Vec<&str> = input.lines().map(|l| if l.trim_start().starts_with('*') { "" } else { l }).collect();
+            grid::read_grid(b, &grid.join("\n"), Some(Encoding::OneBased), None)?;
+        },
+        Format::Jigsaw => {
+            // the layout rows come first, the remaining lines hold the givens and are kept as in .sdk
+            let mut symbols: Vec<String> = Vec::new();
+            let mut grid = String::new();
+            let mut line_size = 0;
+            for (line_no, line) in input.lines().enumerate() {
+                if let Some(header) = line.trim().strip_prefix('#') {
+                    let mut chars = header.chars();
+                    if let Some(code) = chars.next() {
+                        metadata.push(code, chars.as_str().trim());
+                    }
+                } else if grid::is_data_line(line) && (line_size == 0 || symbols.len() < line_size * line_size) {
+                    let row = grid::row_fields(line);
+                    if line_size == 0 && !grid::LINE_SIZES.contains(&row.len()) {
+                        return Err(ParseError::WrongRowLength { line: line_no, length: row.len(), valid: grid::LINE_SIZES.to_vec() });
+                    } else if line_size != 0 && row.len() != line_size {
+                        return Err(ParseError::WrongRowLength { line: line_no, length: row.len(), valid: vec!(line_size) });
+                    }
+                    line_size = row.len();
+                    symbols.extend(row.into_iter().map(|(_, s)| s));
+                } else {
+                    grid.push_str(line);
+                }
+                grid.push('\n');
+            }
+            if line_size == 0 || symbols.len() != line_size * line_size {
+                return Err(ParseError::WrongRowCount { rows: symbols.len() / line_size.max(1), expected: line_size });
+            }
+
+            // regions are numbered in the order of their first field
+            let mut names: Vec<&String> = Vec::new();
+            let regions: Vec<usize> = symbols.iter().map(|s| match names.iter().position(|n| *n == s) {
+                Some(region) => region,
+                None => {
+                    names.push(s);
+                    names.len() - 1
+                },
+            }).collect();
+
+            // boxes of single lines add no constraints until the regions are set
+            grid::read_grid(b, &grid, Some(Encoding::OneBased), Some(Shape::new(1, line_size)))?;
+            b.shape = Shape::for_line_size(line_size).unwrap_or(b.shape);
+            b.set_regions(&regions)?;
+        },
+        Format::Killer => {
+            // the layout rows come first, sum lines are told apart by their '=', the remaining lines hold the givens
+            let mut symbols: Vec<(usize, String)> = Vec::new();
+            let mut sums: Vec<(usize, String, String)> = Vec::new();
+            let mut grid = String::new();
+            let mut line_size = 0;
+            let mut line_offset = 0;
+            for (line_no, line) in input.lines().enumerate() {
+                let offset = line_offset;
+                line_offset += line.chars().count() + 1;
+                if let Some(header) = line.trim().strip_prefix('#') {
+                    let mut chars = header.chars();
+                    if let Some(code) = chars.next() {
+                        metadata.push(code, chars.as_str().trim());
+                    }
+                } else if grid::is_data_line(line) && line.contains('=') {
+                    for (position, token) in grid::chunks(line) {
+                        match token.split_once('=') {
+                            Some((symbol, sum)) => sums.push((offset + position, symbol.to_string(), sum.to_string())),
+                            None => return Err(ParseError::InvalidToken { position: offset + position, token }),
+                        }
+                    }
+                } else if grid::is_data_line(line) && (line_size == 0 || symbols.len() < line_size * line_size) {
+                    // numbers are separated by spaces, letters may be packed
+                    let row: Vec<(usize, String)> = if line.trim().contains(char::is_whitespace) {
+                        grid::chunks(line)
+                    } else {
+                        line.chars().enumerate().filter(|(_, c)| !c.is_whitespace()).map(|(i, c)| (i, c.to_string())).collect()
+                    };
+                    if line_size == 0 && !grid::LINE_SIZES.contains(&row.len()) {
+                        return Err(ParseError::WrongRowLength { line: line_no, length: row.len(), valid: grid::LINE_SIZES.to_vec() });
+                    } else if line_size != 0 && row.len() != line_size {
+                        return Err(ParseError::WrongRowLength { line: line_no, length: row.len(), valid: vec!(line_size) });
+                    }
+                    line_size = row.len();
+                    symbols.extend(row.into_iter().map(|(i, s)| (offset + i, s)));
+                } else {
+                    grid.push_str(line);
+                }
+                grid.push('\n');
+            }
+            if line_size == 0 || symbols.len() != line_size * line_size {
+                return Err(ParseError::WrongRowCount { rows: symbols.len() / line_size.max(1), expected: line_size });
+            }
+
+            // cages are numbered in the order of their first field, every cage needs a sum
+            let mut cages: Vec<(&str, Cage)> = Vec::new();
+            for (idx, (_, symbol)) in symbols.iter().enumerate().filter(|(_, (_, s))| s != ".") {
+                match cages.iter_mut().find(|(name, _)| name == symbol) {
+                    Some((_, cage)) => cage.cells.push(idx),
+                    None => cages.push((symbol, Cage::new(vec!(idx), 0))),
+                }
+            }
+            for (position, symbol, sum) in sums.iter() {
+                let cage = cages.iter_mut().find(|(name, _)| name == symbol);
+                match (cage, sum.parse::<usize>()) {
+                    (Some((_, cage)), Ok(sum)) if cage.sum == 0 && sum > 0 => cage.sum = sum,
+                    _ => return Err(ParseError::InvalidToken { position: *position, token: format!("{}={}", symbol, sum) }),
+                }
+            }
+            if let Some((name, _)) = cages.iter().find(|(_, cage)| cage.sum == 0) {
+                let (position, _) = symbols.iter().find(|(_, s)| s == name).unwrap();
+                return Err(ParseError::InvalidToken { position: *position, token: name.to_string() });
+            }
+
+            // killer puzzles often come without givens
+            if grid.lines().any(grid::is_data_line) {
+                grid::read_grid(b, &grid, Some(Encoding::OneBased), None)?;
+                if b.line_size != line_size {
+                    return Err(ParseError::WrongRowCount { rows: b.line_size, expected: line_size });
+                }
+            } else {
+                *b = Board::empty(Shape::for_line_size(line_size).unwrap_or(Shape::new(1, line_size)));
+            }
+            b.set_cages(cages.into_iter().map(|(_, cage)| cage).collect())?;
+        },
+    }
+    Ok(metadata)
+}
+
+pub fn print_format(b: &Board, format: Format, metadata: &Metadata) -> String {  // metadata is only stored by .sdk, .jigsaw and .killer
+    let mut res = String::new();
+    match format {
+        Format::Sdk => {
+            for (code, value) in metadata.entries() {
+                res.push_str(&format!("#{}{}\n", code, value));
+            }
+            for row in rows(b) {
+                res.push_str(&format!("{}\n", row));
+            }
+        },
+        Format::Sdm => {
+            // "00" is no number, two digit fields are left empty as in the one-line format
+            res = b.print_with(false, encoding(b));
+            if encoding(b).supports(b.line_size) {
+                res = res.replace('.', "0");
+            }
+        },
+        Format::Ss => {
+            let rows = rows(b);
+            let width = rows.first().map_or(0, |r| r.chars().count() / b.line_size);
+            let quads_per_line = b.line_size / b.shape.cols;
+            let box_line: Vec<String> = (0..quads_per_line).map(|_| "-".repeat(width * b.shape.cols)).collect();
+            let frame = format!("*{}*\n", "-".repeat(width * b.line_size + quads_per_line - 1));
+            res.push_str(&frame);
+            for (i, row) in rows.iter().enumerate() {
+                if i > 0 && i % b.shape.rows == 0 {
+                    res.push_str(&format!("|{}|\n", box_line.join("+")));
+                }
+                let chars: Vec<char> = row.chars().collect();
+                let boxes: Vec<String> = chars.chunks(width * b.shape.cols).map(|c| c.iter().collect()).collect();
+                res.push_str(&format!("|{}|\n", boxes.join("|")));
+            }
+            res.push_str(&frame);
+        },
+        Format::Jigsaw => {
+            for (code, value) in metadata.entries() {
+                res.push_str(&format!("#{}{}\n", code, value));
+            }
+            // regions are named in the order of their first field as they are numbered when read
+            let mut order: Vec<usize> = Vec::new();
+            let regions: Vec<usize> = b.regions().into_iter().map(|r| match order.iter().position(|&o| o == r) {
+                Some(name) => name,
+                None => {
+                    order.push(r);
+                    order.len() - 1
+                },
+            }).collect();
+            for line in regions.chunks(b.line_size.max(1)) {
+                let symbols: Vec<String> = line.iter().map(|&r| region_symbol(r, b.line_size)).collect();
+                res.push_str(&format!("{}\n", symbols.join(if b.line_size > 26 { " " } else { "" })));
+            }
+            res.push('\n');
+            for row in rows(b) {
+                res.push_str(&format!("{}\n", row));
+            }
+        },
+        Format::Killer => {
+            for (code, value) in metadata.entries() {
+                res.push_str(&format!("#{}{}\n", code, value));
+            }
+            // cages are named in the order of their first field as they are numbered when read
+            let mut order: Vec<usize> = Vec::new();
+            let names: Vec<Option<usize>> = (0..b.content.len()).map(|idx| b.cage_of(idx).map(|c| match order.iter().position(|&o| o == c) {
+                Some(name) => name,
+                None => {
+                    order.push(c);
+                    order.len() - 1
+                },
+            })).collect();
+            let count = b.cages().len();
+            for line in names.chunks(b.line_size.max(1)) {
+                let symbols: Vec<String> = line.iter().map(|c| c.map_or(".".to_string(), |c| cage_symbol(c, count))).collect();
+                res.push_str(&format!("{}\n", symbols.join(if count > 52 { " " } else { "" })));
+            }
+            res.push('\n');
+            let sums: Vec<String> = order.iter().enumerate().map(|(name, &c)| format!("{}={}", cage_symbol(name, count), b.cages()[c].sum)).collect();
+            for line in sums.chunks(b.line_size.max(1)) {
+                res.push_str(&format!("{}\n", line.join(" ")));
+            }
+            if b.iter().any(|f| matches!(f, Field::Number(_))) {
+                res.push('\n');
+                for row in rows(b) {
+                    res.push_str(&format!("{}\n", row));
+                }
+            }
+        },
+    }
+    res
+}
@@ -0,0 +1,87 @@
+use crate::LogicalSolve;
+use crate::Technique;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Difficulty {
+    Easy,        // singles and cage combinations
+    Medium,      // intersections and pairs
+    Hard,        // triples, Swordfish and XY-Wing
+    Expert,      // quads and coloring
+    Diabolical,  // the logical solver gets stuck, guessing is needed
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Grade {
+    pub difficulty: Difficulty,
+    pub score: f32,
+    pub hardest: Option<Technique>,
+    pub hardest_count: usize,  // how often the hardest technique was applied
+}
+
+const REPEAT_LIMIT: usize = 3;   // a technique needed more often than this raises the difficulty by one level
+const GUESSING_SCORE: f32 = 9.0;
+
+impl Difficulty {
+    pub const ALL: [Difficulty; 5] = [
+        Difficulty::Easy,
+        Difficulty::Medium,
+        Difficulty::Hard,
+        Difficulty::Expert,
+        Difficulty::Diabolical,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Difficulty::Easy       => "Easy",
+            Difficulty::Medium     => "Medium",
+            Difficulty::Hard       => "Hard",
+            Difficulty::Expert     => "Expert",
+            Difficulty::Diabolical => "Diabolical",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Difficulty::ALL.into_iter().find(|d| d.name().eq_ignore_ascii_case(name))
+    }
+
+    fn of_technique(technique: Technique) -> Self {
+        match technique {
+            Technique::NakedSingle | Technique::HiddenSingle | Technique::CageCombination => Difficulty::Easy,
+            Technique::PointingPair | Technique::BoxLineReduction | Technique::NakedPair |
+            Technique::XWing | Technique::HiddenPair => Difficulty::Medium,
+            Technique::NakedTriple | Technique::Swordfish | Technique::HiddenTriple |
+            Technique::XYWing => Difficulty::Hard,
+            Technique::NakedQuad | Technique::HiddenQuad | Technique::SimpleColoring => Difficulty::Expert,
+        }
+    }
+
+    fn harder(&self) -> Self {
+        match self {
+            Difficulty::Easy   => Difficulty::Medium,
+            Difficulty::Medium => Difficulty::Hard,
+            _                  => Difficulty::Expert,
+        }
+    }
+}
+
+pub fn grade(solve: &LogicalSolve) -> Grade {
+    let hardest = solve.steps.iter().map(|step| step.technique).max();
+    let hardest_count = solve.steps.iter().filter(|step| Some(step.technique) == hardest).count();
+
+    if !solve.is_solved() {
+        return Grade { difficulty: Difficulty::Diabolical, score: GUESSING_SCORE, hardest, hardest_count };
+    }
+
+    match hardest {
+        Some(technique) => {
+            // singles are needed on every board, repetitions only count for the harder techniques
+            let mut difficulty = Difficulty::of_technique(technique);
+            if difficulty != Difficulty::Easy && hardest_count > REPEAT_LIMIT {
+                difficulty = difficulty.harder();
+            }
+            let score = technique.rating() + 0.01 * (hardest_count.min(10) - 1) as f32;
+            Grade { difficulty, score, hardest, hardest_count }
+        },
+        None => Grade { difficulty: Difficulty::Easy, score: 0.0, hardest, hardest_count },
+    }
+}
@@ -0,0 +1,203 @@
+use crate::Board;
+use crate::Encoding;
+use crate::ParseError;
+use crate::Shape;
+
+// Multi-line grids as typed by hand or printed by print(true): one row per line,
+// fields either separated by whitespace or packed, box separators '|' and separator
+// lines of '-', '+', '=' are ignored, '.', '_' and '0' mark empty fields and lines
+// starting with '#' or "//" are comments.
+
+pub const LINE_SIZES: [usize; 12] = [4, 6, 8, 9, 10, 12, 16, 25, 36, 49, 64, 81];  // square boxes and the rectangular 2x3, 2x4, 2x5 and 3x4
+
+#[derive(Clone, Copy)]
+enum Layout {
+    Separated,     // every chunk is one field
+    Packed(usize), // chunks are concatenated, fields have a fixed width
+}
+
+pub struct GridReader {  // collects the lines of a grid from a stream until all rows are present
+    text: String,
+    rows: usize,
+    line_size: usize,
+}
+
+pub fn is_data_line(line: &str) -> bool {
+    let line = line.trim();
+    !(line.is_empty() ||
+      line.starts_with('#') ||
+      line.starts_with("//") ||
+      line.chars().all(|c| "-+=|".contains(c) || c.is_whitespace()))
+}
+
+pub fn chunks(line: &str) -> Vec<(usize, String)> {  // (character offset, text) split at whitespace and box separators
+    let mut res: Vec<(usize, String)> = Vec::new();
+    let mut current: Option<(usize, String)> = None;
+    for (i, c) in line.chars().enumerate() {
+        if c.is_whitespace() || c == '|' {
+            res.extend(current.take());
+        } else {
+            current.get_or_insert((i, String::new())).1.push(c);
+        }
+    }
+    res.extend(current);
+    res
+}
+
+fn layout(chunks: &[(usize, String)]) -> Option<(Layout, usize)> {  // layout and line size from the first row
+    if LINE_SIZES.contains(&chunks.len()) && chunks.iter().all(|(_, c)| c.chars().count() <= 2) {
+        return Some((Layout::Separated, chunks.len()));
+    }
+
+    let len = row_length(chunks);
+    if LINE_SIZES.contains(&len) {
+        Some((Layout::Packed(1), len))
+    } else if len.is_multiple_of(2) && len / 2 > 9 && LINE_SIZES.contains(&(len / 2)) {
+        Some((Layout::Packed(2), len / 2))
+    } else {
+        None
+    }
+}
+
+pub fn row_fields(line: &str) -> Vec<(usize, String)> {  // fields of a single row, the layout is detected from the row alone
+    let chunks = chunks(line);
+    match layout(&chunks) {
+        Some((layout, _)) => fields(chunks, layout),
+        None => chunks,
+    }
+}
+
+fn fields(chunks: Vec<(usize, String)>, layout: Layout) -> Vec<(usize, String)> {
+    match layout {
+        Layout::Separated => chunks,
+        Layout::Packed(width) => {
+            let chars: Vec<(usize, char)> = chunks.iter()
+                .flat_map(|(offset, c)| c.chars().enumerate().map(move |(i, c)| (offset + i, c)))
+                .collect();
+            chars.chunks(width).map(|f| (f[0].0, f.iter().map(|x| x.1).collect())).collect()
+        },
+    }
+}
+
+fn row_length(chunks: &[(usize, String)]) -> usize {
+    chunks.iter().map(|(_, c)| c.chars().count()).sum()
+}
+
+pub fn read_grid(b: &mut Board, input: &str, encoding: Option<Encoding>, boxes: Option<Shape>) -> Result<(), ParseError> {  // encoding of grids using letters, detected if None, boxes other than the standard shape
+    let mut rows: Vec<Vec<(usize, String)>> = Vec::new();
+    let mut shape: Option<(Layout, usize)> = None;
+
+    let mut line_offset = 0;
+    for (line_no, line) in input.split('\n').enumerate() {
+        let offset = line_offset;
+        line_offset += line.chars().count() + 1;
+        if !is_data_line(line) {
+            continue;
+        }
+
+        let chunks: Vec<(usize, String)> = chunks(line).into_iter().map(|(o, c)| (o + offset, c)).collect();
+        let (layout, line_size) = match shape {
+            Some(s) => s,
+            None => {
+                let s = layout(&chunks).ok_or(ParseError::WrongRowLength { line: line_no, length: row_length(&chunks), valid: LINE_SIZES.to_vec() })?;
+                shape = Some(s);
+                s
+            },
+        };
+
+        let row = fields(chunks, layout);
+        if row.len() != line_size {
+            return Err(ParseError::WrongRowLength { line: line_no, length: row.len(), valid: vec!(line_size) });
+        }
+        rows.push(row);
+    }
+
+    let line_size = shape.map_or(0, |s| s.1);
+    if line_size == 0 || rows.len() != line_size {
+        return Err(ParseError::WrongRowCount { rows: rows.len(), expected: line_size });
+    }
+
+    // translate to the one-line format, '0' is a number in grids using letters
+    let fields: Vec<(usize, String)> = rows.into_iter().flatten().collect();
+    let letters = fields.iter().any(|(_, f)| f.chars().any(|c| c.is_ascii_alphabetic()));
+    let width = if letters { 1 } else { Encoding::Numbers.width(line_size) };
+    let mut text = String::new();
+    for (position, field) in fields.iter() {
+        let len = field.chars().count();
+        if field.chars().all(|c| c == '.' || c == '_' || (!letters && c == '0')) {
+            text.push_str(&".".repeat(width));
+        } else if len > width {
+            return Err(ParseError::InvalidToken { position: *position, token: field.clone() });
+        } else {
+            text.push_str(&"0".repeat(width - len));
+            text.push_str(field);
+        }
+    }
+
+    // report positions within the grid
+    let res = b.read_encoded(&text, encoding.filter(|_| letters), boxes);
+    res.map_err(|e| match e {
+        ParseError::InvalidToken { position, .. } => {
+            let (position, token) = fields[position / width].clone();
+            ParseError::InvalidToken { position, token }
+        },
+        ParseError::ValueOutOfRange { position, value, max } =>
+            ParseError::ValueOutOfRange { position: fields[position / width].0, value, max },
+        e => e,
+    })
+}
+
+impl Default for GridReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GridReader {
+    pub fn new() -> Self {
+        GridReader { text: String::new(), rows: 0, line_size: 0 }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows == 0
+    }
+
+    pub fn push_line(&mut self, line: &str) -> Result<Option<String>, ParseError> {  // the complete grid once its last row is pushed
+        let line = line.trim_end_matches(['\n', '\r']);
+        if !is_data_line(line) {
+            if !self.is_empty() {
+                self.text.push_str(line);
+                self.text.push('\n');
+            }
+            return Ok(None);
+        }
+
+        if self.is_empty() {
+            let chunks = chunks(line);
+            match layout(&chunks) {
+                Some((_, line_size)) => self.line_size = line_size,
+                None => return Err(ParseError::WrongRowLength { line: 0, length: row_length(&chunks), valid: LINE_SIZES.to_vec() }),
+            }
+        }
+
+        self.text.push_str(line);
+        self.text.push('\n');
+        self.rows += 1;
+        if self.rows == self.line_size {
+            let text = std::mem::take(&mut self.text);
+            self.rows = 0;
+            Ok(Some(text))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn finish(&mut self) -> Result<(), ParseError> {  // error if a grid is left incomplete
+        if self.is_empty() {
+            return Ok(());
+        }
+        let (rows, expected) = (self.rows, self.line_size);
+        *self = GridReader::new();
+        Err(ParseError::WrongRowCount { rows, expected })
+    }
+}
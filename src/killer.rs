@@ -0,0 +1,71 @@
+use crate::Candidates;
+
+// Killer cages: the numbers within a cage are distinct and add up to its sum.
+// Sums count from one as printed, candidates are zero-based like the numbers stored in the board.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Cage {
+    pub cells: Vec<usize>,  // field indices, line * line_size + col
+    pub sum: usize,
+}
+
+impl Cage {
+    pub fn new(cells: Vec<usize>, sum: usize) -> Self {
+        Cage { cells, sum }
+    }
+
+    pub fn combinations(line_size: usize, size: usize, sum: usize) -> Vec<Candidates> {  // sum-combination table, every set of size distinct numbers adding up to sum
+        let mut res = Vec::new();
+        collect(line_size, 0, size, sum, Candidates::empty(), &mut res);
+        res
+    }
+}
+
+fn collect(line_size: usize, from: usize, size: usize, sum: usize, chosen: Candidates, res: &mut Vec<Candidates>) {
+    if size == 0 {
+        if sum == 0 {
+            res.push(chosen);
+        }
+        return;
+    }
+
+    // the largest numbers left have to reach the sum, the smallest ones must not exceed it
+    let largest = (line_size + 1 - size.min(line_size)..=line_size).sum::<usize>();
+    if size > line_size - from || sum > largest {
+        return;
+    }
+    for val in from..line_size {
+        let smallest = size * (val + 1) + size * (size - 1) / 2;
+        if smallest > sum {
+            break;
+        }
+        let mut next = chosen;
+        next.insert(val);
+        collect(line_size, val + 1, size - 1, sum - (val + 1), next, res);
+    }
+}
+
+pub fn options(line_size: usize, sum: usize, placed: &[usize], lists: &[Candidates]) -> Vec<Candidates> {  // option lists of the empty cells of a cage narrowed to the combinations left, all empty on contradiction
+    let mut allowed = vec!(Candidates::empty(); lists.len());
+    let placed_sum: usize = placed.iter().map(|n| n + 1).sum();
+    if placed_sum > sum {
+        return allowed;
+    }
+    let placed: Candidates = placed.iter().copied().collect();
+
+    for combo in Cage::combinations(line_size, lists.len(), sum - placed_sum) {
+        if !combo.intersection(placed).is_empty() {
+            continue;
+        }
+
+        // every cell needs a number of the combination and every number a cell
+        let fits: Vec<Candidates> = lists.iter().map(|list| list.intersection(combo)).collect();
+        if fits.iter().any(|f| f.is_empty()) || fits.iter().fold(Candidates::empty(), |acc, f| acc.union(*f)) != combo {
+            continue;
+        }
+        for (a, f) in allowed.iter_mut().zip(fits) {
+            *a = a.union(f);
+        }
+    }
+    allowed
+}
@@ -1,24 +1,105 @@
 mod board_iterator;
+mod candidates;
+mod dlx;
+mod encoding;
+mod error;
+mod format;
+mod grade;
+mod grid;
+mod killer;
+mod logic;
+mod multi;
+mod pencil;
+mod render;
+#[cfg(feature = "serde")]
+mod serialize;
+mod solutions;
+
+use std::cmp::Reverse;
+use std::ops::RangeInclusive;
+use std::rc::Rc;
 
 use rand::Rng;
+use rand::seq::SliceRandom;
 pub use crate::board_iterator::BoardIter;
+pub use crate::candidates::Candidates;
+pub use crate::encoding::Encoding;
+pub use crate::error::ParseError;
+pub use crate::format::Format;
+pub use crate::format::Metadata;
+pub use crate::grade::Difficulty;
+pub use crate::grade::Grade;
+pub use crate::grid::GridReader;
+pub use crate::killer::Cage;
+pub use crate::logic::LogicalSolve;
+pub use crate::logic::Step;
+pub use crate::logic::Technique;
+pub use crate::multi::MultiBoard;
+pub use crate::multi::MultiLayout;
+pub use crate::multi::MultiSolutions;
+pub use crate::render::Colors;
+pub use crate::render::RenderOptions;
+pub use crate::solutions::Solutions;
 
-#[derive(Clone)]
+#[derive(Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "serialize::FieldData", try_from = "serialize::FieldData"))]
 pub enum Field {
     Number(usize),
-    OptionList(Vec<usize>),
+    OptionList(Candidates),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum House {
+    Line(usize),
+    Col(usize),
+    Quad(usize),
+    Diagonal(usize),  // 0 from the top left to the bottom right corner, 1 from the top right to the bottom left
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Shape {  // boxes of rows x cols fields, the board has rows * cols lines
+    pub rows: usize,
+    pub cols: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Origin {
+    Given,    // clue of the puzzle as read or generated, locked
+    Derived,  // filled in by a solver
+    Entered,  // entered by the player
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Solver {
+    Stack,         // depth-first search with single option propagation
+    DancingLinks,  // exact cover search with Knuth's algorithm X
 }
 
 enum SolverStep {
     Unsolvable,
-    BranchOnOptionList(usize),
+    BranchOnOptionList(usize, usize),  // field and the number to try first
     Solved,
 }
 
+struct Propagation {  // houses and cages with changed cells, each one is queued once until it is scanned
+    houses: Vec<House>,
+    cages: Vec<usize>,
+    queued_houses: Vec<bool>,
+    queued_cages: Vec<bool>,
+}
+
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "serialize::BoardData", try_from = "serialize::BoardData"))]
 pub struct Board {
     content: Vec<Field>,
-    base: usize,
+    origins: Vec<Option<Origin>>,  // where the number of a field came from, None for empty fields
+    shape: Shape,
     line_size: usize,
+    regions: Option<Rc<[usize]>>,  // jigsaw region of every field, None for the boxes of the shape, shared between clones
+    diagonals: bool,  // X-Sudoku, both main diagonals hold every number once
+    cages: Rc<[Cage]>,  // killer cages, fields outside of cages only follow the houses, shared between clones
 }
 
 // Order       Line        Board        String
@@ -30,92 +111,307 @@ pub struct Board {
 //   7      7 * 7 = 49
 //   8      8 * 8 = 64
 //   9      9 * 9 = 81
+//
+// Boxes      Line        Board        String
+//  2x3     2 * 3 =  6   6 *  6 =  36     36
+//  2x4     2 * 4 =  8   8 *  8 =  64     64
+//  2x5     2 * 5 = 10  10 * 10 = 100    200
+//  3x4     3 * 4 = 12  12 * 12 = 144    288
+
+impl Shape {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        Shape { rows, cols }
+    }
+
+    pub fn square(base: usize) -> Self {
+        Shape { rows: base, cols: base }
+    }
+
+    pub fn line_size(&self) -> usize {
+        self.rows * self.cols
+    }
+
+    pub fn for_line_size(line_size: usize) -> Option<Self> {  // standard shape of a supported line size, boxes are at least as wide as high
+        if !grid::LINE_SIZES.contains(&line_size) {
+            return None;
+        }
+        let rows = (2..=line_size).take_while(|r| r * r <= line_size).filter(|r| line_size.is_multiple_of(*r)).last()?;
+        Some(Shape::new(rows, line_size / rows))
+    }
+
+    pub fn name(&self) -> String {
+        format!("{}x{}", self.rows, self.cols)
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {  // "2x3" for boxes of 2 lines and 3 columns, a single number for square boxes
+        let shape = match name.split_once(['x', 'X']) {
+            Some((rows, cols)) => Shape::new(rows.trim().parse().ok()?, cols.trim().parse().ok()?),
+            None => Shape::square(name.trim().parse().ok()?),
+        };
+        if shape.is_supported() { Some(shape) } else { None }
+    }
+
+    fn is_supported(&self) -> bool {
+        self.rows >= 2 && self.cols >= 2 && Encoding::Numbers.supports(self.line_size())
+    }
+}
+
+impl From<usize> for Shape {
+    fn from(base: usize) -> Self {
+        Shape::square(base)
+    }
+}
+
+impl Default for Board {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl Board {
     pub fn new() -> Self {
         Board {
             content: Vec::new(),
-            base: 0,
+            origins: Vec::new(),
+            shape: Shape::square(0),
             line_size: 0,
+            regions: None,
+            diagonals: false,
+            cages: Rc::default(),
         }
     }
 
-    pub fn clone(&self) -> Self {
-        Board {
-            content: self.content.to_vec(),
-            base: self.base,
-            line_size: self.line_size,
-        }
-    }
-
-    fn reset(&mut self, base: usize) {
+    fn reset(&mut self, shape: Shape) {
         self.content.clear();
-        self.base = base;
-        self.line_size = self.base.pow(2);
+        self.shape = shape;
+        self.line_size = shape.line_size();
+        self.regions = None;
+        self.diagonals = false;
+        self.cages = Rc::default();
 
         let board_size = self.line_size.pow(2);
         self.content.reserve_exact(board_size);
         for _ in 0..board_size {
-            self.content.push( Field::OptionList( (0..self.line_size).collect() ) );
+            self.content.push( Field::OptionList( Candidates::full(self.line_size) ) );
+        }
+        self.origins = vec!(None; board_size);
+    }
+
+    fn fix_givens(&mut self) {  // the numbers currently on the board become the givens
+        self.origins = self.content.iter().map(|f| if let Field::Number(_) = f { Some(Origin::Given) } else { None }).collect();
+    }
+
+    fn rebuild<F>(&mut self, keep: F)  // board with the numbers whose origin is kept, candidates are derived from scratch
+    where
+        F: Fn(Origin) -> bool
+    {
+        let numbers: Vec<(usize, usize, Origin)> = (0..self.content.len())
+            .filter_map(|idx| match (self.content[idx], self.origins[idx]) {
+                (Field::Number(n), Some(origin)) if keep(origin) => Some((idx, n, origin)),
+                _ => None,
+            })
+            .collect();
+
+        self.reset_content();
+        for (idx, n, origin) in numbers {
+            self.set_num_index(idx, n);
+            self.origins[idx] = Some(origin);
+        }
+    }
+
+    fn reset_content(&mut self) {  // empty board keeping regions, diagonals and cages
+        let (regions, diagonals, cages) = (self.regions.take(), self.diagonals, std::mem::take(&mut self.cages));
+        self.reset(self.shape);
+        self.regions = regions;
+        self.diagonals = diagonals;
+        self.cages = cages;
+    }
+
+    fn change_houses<F>(&mut self, change: F) -> Result<(), ParseError>  // numbers are placed again after the houses changed, conflicts are reported
+    where
+        F: FnOnce(&mut Board)
+    {
+        let numbers: Vec<(usize, usize, Option<Origin>)> = (0..self.content.len())
+            .filter_map(|idx| self.get_num_index(idx).map(|n| (idx, n, self.origins[idx])))
+            .collect();
+
+        change(self);
+        self.reset_content();
+        for (idx, n, origin) in numbers {
+            if !self.set_num_index(idx, n) {
+                return Err(self.conflict(idx, n));
+            }
+            self.origins[idx] = origin;
         }
+        Ok(())
+    }
+
+    pub fn read(&mut self, input: &str) -> Result<(), ParseError> {  // encoding is detected from the input length and symbols
+        self.read_encoded(input, None, None)
+    }
+
+    pub fn read_with(&mut self, input: &str, encoding: Encoding) -> Result<(), ParseError> {
+        self.read_encoded(input, Some(encoding), None)
     }
 
-    pub fn read(&mut self, input: &str) -> bool {
-        let len = input.len();
+    pub fn read_with_shape(&mut self, input: &str, shape: Shape) -> Result<(), ParseError> {  // boxes other than the standard shape of the line size, like 2x6
+        self.read_encoded(input, None, Some(shape))
+    }
+
+    pub fn read_grid(&mut self, input: &str) -> Result<(), ParseError> {  // multi-line grid, also reads what print(true) produces, jigsaw regions only round trip with Format::Jigsaw
+        grid::read_grid(self, input, None, None)
+    }
+
+    pub fn read_format(&mut self, input: &str, format: Format) -> Result<Metadata, ParseError> {  // a single puzzle, see Format::puzzles for collections
+        format::read_format(self, input, format)
+    }
+
+    pub fn read_pencil_marks(&mut self, input: &str) -> Result<(), ParseError> {  // candidates of every field as wide grid or compact string
+        pencil::read_pencil_marks(self, input)
+    }
+
+    fn read_encoded(&mut self, input: &str, encoding: Option<Encoding>, shape: Option<Shape>) -> Result<(), ParseError> {  // never panics, any malformed input is reported as error
+        // work on characters, slicing bytes could split multi-byte characters
+        let chars: Vec<char> = input.chars().collect();
+        let len = chars.len();
         
-        // find base
+        // find line size and encoding
         let mut board_size = 0;
-        self.base = 0;
+        let mut found = None;
+        let mut ambiguous = None;
+        self.shape = Shape::square(0);
         self.line_size = 0;
 
-        for i in 2usize..10usize {
-            let p = i.pow(4);
-            if (p == len) || (p == (len / 2)) {
-                self.base = i;
+        let line_sizes: Vec<usize> = match shape {
+            Some(shape) => vec!(shape.line_size()),
+            None => grid::LINE_SIZES.to_vec(),
+        };
+        for &line_size in line_sizes.iter() {
+            let p = line_size.pow(2);
+            found = match encoding {
+                Some(e) if e.supports(line_size) && p * e.width(line_size) == len => Some(e),
+                Some(_) => None,
+                None if p == len => {
+                    if Encoding::is_ambiguous(&chars, line_size) {
+                        ambiguous = Some(line_size);
+                    }
+                    Encoding::detect(&chars, line_size)
+                },
+                None if p * Encoding::Numbers.width(line_size) == len => Some(Encoding::Numbers),
+                None => None,
+            };
+            if found.is_some() {
+                self.shape = shape.or(Shape::for_line_size(line_size)).unwrap_or(Shape::square(0));
                 board_size = p;
                 break;
             }
         }
 
         // bail out if string length not matching
-        if self.base == 0 {
-            return false;
-        }
+        let encoding = match found {
+            Some(e) => e,
+            None => {
+                self.content.clear();
+                self.origins.clear();
+                if let Some(line_size) = ambiguous {
+                    return Err(ParseError::AmbiguousEncoding { line_size });
+                }
+                let encodings = match encoding {
+                    Some(e) => vec!(e),
+                    None => Encoding::ALL.to_vec(),
+                };
+                let mut valid: Vec<usize> = line_sizes.into_iter()
+                    .flat_map(|l| encodings.iter().filter(move |e| e.supports(l)).map(move |e| l.pow(2) * e.width(l)))
+                    .collect();
+                valid.sort();
+                valid.dedup();
+                return Err(ParseError::WrongLength { length: len, valid });
+            },
+        };
 
         // reset board content
-        self.reset(self.base);
+        self.reset(self.shape);
 
         // parse string and fill numbers
-        let num_size = if self.base <= 3 {1usize} else {2usize};
+        let num_size = encoding.width(self.line_size);
         for i in 0..board_size {
-            let i = i;
-            let num = &input[(i*num_size)..((i+1)*num_size)];
-            if let Ok(num) = num.parse::<usize>() {
-                if !self.set_num_index(i, num - 1) {
-                    return false;
-                }
+            let position = i * num_size;
+            let token: String = chars[position..(position + num_size)].iter().collect();
+            if token.chars().all(|c| c == '.') {
+                continue;
+            }
+
+            let num = encoding.decode(&token).ok_or(ParseError::InvalidToken { position, token: token.clone() })?;
+            if num == 0 || !self.check_valid_number(num - 1) {
+                return Err(ParseError::ValueOutOfRange { position, value: num, max: self.line_size });
+            }
+
+            if !self.set_num_index(i, num - 1) {
+                return Err(self.conflict(i, num - 1));
             }
         }
 
-        return true;
+        self.fix_givens();
+        Ok(())
     }
 
     pub fn print(&self, pretty_print: bool) -> String {
+        self.print_with(pretty_print, Encoding::Numbers)
+    }
+
+    pub fn print_format(&self, format: Format, metadata: &Metadata) -> String {
+        format::print_format(self, format, metadata)
+    }
+
+    pub fn render(&self, options: &RenderOptions) -> String {  // Unicode box drawing, see RenderOptions
+        render::render(self, options)
+    }
+
+    pub fn print_pencil_marks(&self) -> String {  // wide grid listing the candidates of every field
+        pencil::print_pencil_marks(self)
+    }
+
+    pub fn print_candidates(&self) -> String {  // compact string with line_size symbols per field
+        pencil::print_candidates(self)
+    }
+
+    pub fn print_with(&self, pretty_print: bool, encoding: Encoding) -> String {  // boards too large for the encoding are printed as numbers
+        self.print_styled(pretty_print, encoding, |_, text| text)
+    }
+
+    pub fn print_colored(&self, pretty_print: bool, options: &RenderOptions) -> String {  // print with ANSI colors if enabled by the options
+        render::print_colored(self, pretty_print, options)
+    }
+
+    fn print_styled<F>(&self, pretty_print: bool, encoding: Encoding, style: F) -> String  // style wraps the text of every field
+    where
+        F: Fn(usize, String) -> String
+    {
+        let encoding = if encoding.supports(self.line_size) { encoding } else { Encoding::Numbers };
         let mut res = String::from("");
-        let width = if self.line_size > 9 {2} else {1};
+        let width = encoding.width(self.line_size);
         for i in 0..self.content.len() {
             match self.content[i] {
-                Field::Number(n) => res = format!("{0}{1:02$}", res, n + 1, width),
-                _ => res = format!("{0}{1:.>2$}", res, "", width),
+                Field::Number(n) => res = format!("{}{}", res, style(i, encoding.encode(n, self.line_size))),
+                _ => res = format!("{}{}", res, style(i, ".".repeat(width))),
             }
 
-            if pretty_print {
+            if pretty_print && self.regions.is_some() {
+                // jigsaw regions: '|' between fields of different regions, no separator lines
                 match i {
-                    n if ((n / self.line_size) % self.base == self.base - 1) && (n % self.line_size == self.line_size - 1) =>
-                        res = format!("{0}\n{1:->2$}\n", res, "", width * self.line_size + self.base - 1),
                     n if n % self.line_size == self.line_size - 1 =>
                         res = format!("{}\n", res),
-                    n if n % self.base == self.base - 1 =>
+                    n if self.regions.as_ref().is_some_and(|r| r[n] != r[n + 1]) =>
+                        res = format!("{}|", res),
+                    _ => res = format!("{} ", res),
+                }
+            } else if pretty_print {
+                match i {
+                    n if ((n / self.line_size) % self.shape.rows == self.shape.rows - 1) && (n % self.line_size == self.line_size - 1) =>
+                        res = format!("{0}\n{1:->2$}\n", res, "", width * self.line_size + self.line_size / self.shape.cols - 1),
+                    n if n % self.line_size == self.line_size - 1 =>
+                        res = format!("{}\n", res),
+                    n if n % self.shape.cols == self.shape.cols - 1 =>
                         res = format!("{}|", res),
                     _ => (),
                 }
@@ -138,12 +434,13 @@ impl Board {
 
     fn clear_num_index(&mut self, idx: usize) {
         if let Field::Number(i) = self.content[idx] {
-            self.content[idx] = Field::OptionList(vec!(i));
+            self.content[idx] = Field::OptionList(Candidates::single(i));
+            self.origins[idx] = None;
         }
     }
 
     fn set_num_index(&mut self, idx: usize, val: usize) -> bool {
-        if self.base != 0 {
+        if self.line_size != 0 {
             // check inputs
             if !self.check_valid_number(val) {
                 return false;
@@ -162,62 +459,81 @@ impl Board {
             // check line
             for i in 0..self.line_size {
                 let board_index = self.idx_from_line_col(line , i);
-                if board_index != idx {
-                    if !self.update_field(board_index, val) {
-                        return false;
-                    }
+                if board_index != idx && !self.update_field(board_index, val) {
+                    return false;
                 }
             }
 
             // check column
             for i in 0..self.line_size {
                 let board_index = self.idx_from_line_col(i, col);
-                if board_index != idx {
-                    if !self.update_field(board_index, val) {
+                if board_index != idx && !self.update_field(board_index, val) {
+                    return false;
+                }
+            }
+
+            // check quadrant
+            if self.regions.is_some() {
+                for board_index in self.house_cells(House::Quad(self.quad_of(line, col))) {
+                    if board_index != idx && !self.update_field(board_index, val) {
                         return false;
                     }
                 }
+            } else {
+                let (quadrant_start_line, quadrant_start_col) = self.quadrant_start_from_line_col(line, col);
+                for i in 0..self.shape.rows {
+                    for j in 0..self.shape.cols {
+                        let board_index = self.idx_from_line_col(quadrant_start_line + i, quadrant_start_col + j);
+                        if board_index != idx && !self.update_field(board_index, val) {
+                            return false;
+                        }
+                    }
+                }
             }
 
-            // check quadrant
-            let (quadrant_start_line, quadrant_start_col) = self.quadrant_start_from_line_col(line, col);
-            for i in 0..self.base {
-                for j in 0..self.base {
-                    let board_index = self.idx_from_line_col(quadrant_start_line + i, quadrant_start_col + j);
-                    if board_index != idx {
-                        if !self.update_field(board_index, val) {
+            // check diagonals
+            if self.diagonals {
+                for house in self.houses_of(idx).into_iter().filter(|h| matches!(h, House::Diagonal(_))) {
+                    for board_index in self.house_cells(house) {
+                        if board_index != idx && !self.update_field(board_index, val) {
                             return false;
                         }
-                    } 
+                    }
+                }
+            }
+
+            // check cage
+            let cage = self.cage_of(idx);
+            if let Some(cage) = cage {
+                for board_index in self.cages[cage].cells.clone() {
+                    if board_index != idx && !self.update_field(board_index, val) {
+                        return false;
+                    }
                 }
             }
 
             // insert value
             self.content[idx] = Field::Number(val);
+            self.origins[idx] = Some(Origin::Derived);
+
+            // the numbers of the cage still have to fit its sum, narrowing the options is left to the solvers
+            if let Some(cage) = cage {
+                if self.cage_options(cage).is_none() {
+                    return false;
+                }
+            }
 
             // maybe: update Option list if options for a number have the same line/column
 
             return true;
         }
-        return false;
-    }
-
-    fn get_first_option_list(&self, idx: usize) -> Option<usize> {
-        if let Field::OptionList(list) = &self.content[idx] {
-            if list.len() > 0 {
-                Some(list[0])
-            } else {
-                None
-            }
-        } else {
-            None
-        }
+        false
     }
 
-    fn get_option_list(&self, idx: usize) -> Option<&[usize]> {
-        if let Field::OptionList(list) = &self.content[idx] {
-            if list.len() > 0 {
-                Some(&list)
+    fn get_option_list(&self, idx: usize) -> Option<Candidates> {
+        if let Field::OptionList(list) = self.content[idx] {
+            if !list.is_empty() {
+                Some(list)
             } else {
                 None
             }
@@ -229,9 +545,7 @@ impl Board {
     fn remove_from_option_list(&mut self, idx: usize, val: usize) {
         if self.check_valid_number(val) && self.check_valid_index(idx) {
             if let Field::OptionList(list) = &mut self.content[idx] {
-                if let Some(i) = list.iter().position(|&x| x == val) {
-                    list.swap_remove(i);
-                }
+                list.remove(val);
             }
         }
     }
@@ -242,9 +556,7 @@ impl Board {
             Field::Number(n) if *n == val => false,
             // remove number from option lists
             Field::OptionList(v) => {
-                if let Some(i) = v.iter().position(|&x| x == val) {
-                    v.swap_remove(i);
-                }
+                v.remove(val);
                 true
             },
             _ => true,
@@ -261,15 +573,33 @@ impl Board {
     }
 
     fn quadrant_start_from_line_col(&self, line: usize, col: usize) -> (usize, usize) {
-        ((line / self.base) * self.base, (col / self.base) * self.base)
+        ((line / self.shape.rows) * self.shape.rows, (col / self.shape.cols) * self.shape.cols)
+    }
+
+    fn quadrant_start_from_quad(&self, quad_idx: usize) -> (usize, usize) {  // quadrants are numbered line by line, regions start at their first field
+        if let Some(regions) = &self.regions {
+            return match regions.iter().position(|&r| r == quad_idx) {
+                Some(idx) => self.line_col_from_idx(idx),
+                None => (self.line_size, self.line_size),
+            };
+        }
+        let quads_per_line = self.line_size / self.shape.cols;
+        ((quad_idx / quads_per_line) * self.shape.rows, (quad_idx % quads_per_line) * self.shape.cols)
+    }
+
+    fn quad_of(&self, line: usize, col: usize) -> usize {
+        match &self.regions {
+            Some(regions) => regions[self.idx_from_line_col(line, col)],
+            None => (line / self.shape.rows) * (self.line_size / self.shape.cols) + col / self.shape.cols,
+        }
     }
 
     fn check_valid_index(&self, idx: usize) -> bool {
-        !(idx >= (self.content.len()))
+        idx < self.content.len()
     }
 
     fn check_valid_number(&self, num: usize) -> bool {
-        !(num >= self.line_size)
+        num < self.line_size
     }
 
     fn check_valid_coord(&self, line: usize, col: usize) -> bool {
@@ -278,7 +608,7 @@ impl Board {
         (col < self.line_size)
     }
 
-    pub fn line_iter(&self, line: usize) -> BoardIter {
+    pub fn line_iter(&self, line: usize) -> BoardIter<'_> {
         if self.check_valid_coord(line, 0) {
             BoardIter::new_line_iter(self, line)
         } else {
@@ -286,7 +616,7 @@ impl Board {
         }
     }
 
-    pub fn col_iter(&self, col: usize) -> BoardIter {
+    pub fn col_iter(&self, col: usize) -> BoardIter<'_> {
         if self.check_valid_coord(0, col) {
             BoardIter::new_col_iter(self, col)
         } else {
@@ -294,203 +624,629 @@ impl Board {
         }
     }
 
-    pub fn quad_iter(&self, quad_idx: usize) -> BoardIter {
+    pub fn quad_iter(&self, quad_idx: usize) -> BoardIter<'_> {
         if quad_idx <= self.line_size {
-            let col = (quad_idx * self.base) % self.line_size;
-            BoardIter::new_quad_iter(self, quad_idx, col)
+            let (line, col) = self.quadrant_start_from_quad(quad_idx);
+            BoardIter::new_quad_iter(self, line, col)
         } else {
             BoardIter::new_quad_iter(self, 0, 0)
         }
     }
 
-    pub fn iter(&self) -> BoardIter {
+    pub fn iter(&self) -> BoardIter<'_> {
         BoardIter::new_field_iter(self)
     }
 
-    fn find_single_option<'a, F, G>(&'a self, get_iterator: F, get_idx: G) -> Option<(usize, usize)>  // find a number that has only one option within a quadrant/line/col
-    where
-        F: Fn(usize) -> BoardIter<'a>,
-        G: Fn(usize, usize) -> usize
-    {
-        for q_idx in 0..self.line_size {
-            for n in 0..self.line_size {
-                let iter = get_iterator(q_idx);
-    
-                let contains_number = iter.enumerate().filter(|x|
-                    if let Field::OptionList(l) = x.1 {
-                        l.iter().any(|elem| *elem == n)
-                    } else {
-                        false
-                    }
-                );
-    
-                let mut idx = 0;
-                let mut count = 0;
-                for (current_idx, _) in contains_number {
-                    count += 1;
-                    idx = current_idx;
-                }
-    
-                if count == 1 {
-                    return Some((n, get_idx(idx, q_idx)));
+    pub fn origin(&self, line: usize, col: usize) -> Option<Origin> {  // None for empty fields
+        if self.check_valid_coord(line, col) {
+            self.origins[self.idx_from_line_col(line, col)]
+        } else {
+            None
+        }
+    }
+
+    pub fn enter(&mut self, line: usize, col: usize, num: usize) -> bool {  // zero-based like Field::Number, replaces numbers that are not given
+        if !self.check_valid_coord(line, col) || !self.check_valid_number(num) {
+            return false;
+        }
+
+        let idx = self.idx_from_line_col(line, col);
+        let mut b = self.clone();
+        match b.origins[idx] {
+            Some(Origin::Given) => return false,
+            Some(_) => {
+                b.content[idx] = Field::OptionList(Candidates::empty());
+                b.origins[idx] = None;
+                b.rebuild(|_| true);
+            },
+            None => (),
+        }
+
+        if !b.set_num_index(idx, num) {
+            return false;
+        }
+        b.origins[idx] = Some(Origin::Entered);
+        *self = b;
+        true
+    }
+
+    pub fn erase(&mut self, line: usize, col: usize) -> bool {  // givens are locked
+        match self.origin(line, col) {
+            Some(Origin::Derived) | Some(Origin::Entered) => {
+                let idx = self.idx_from_line_col(line, col);
+                self.content[idx] = Field::OptionList(Candidates::empty());
+                self.origins[idx] = None;
+                self.rebuild(|_| true);
+                true
+            },
+            _ => false,
+        }
+    }
+
+    pub fn reset_to_start(&mut self) {  // remove everything but the givens
+        self.rebuild(|origin| origin == Origin::Given);
+    }
+
+    pub fn shape(&self) -> Shape {
+        self.shape
+    }
+
+    pub fn regions(&self) -> Vec<usize> {  // region of every field, the boxes of the shape unless jigsaw regions are set
+        (0..self.content.len()).map(|idx| {
+            let (line, col) = self.line_col_from_idx(idx);
+            self.quad_of(line, col)
+        }).collect()
+    }
+
+    pub fn has_regions(&self) -> bool {
+        self.regions.is_some()
+    }
+
+    pub fn set_regions(&mut self, regions: &[usize]) -> Result<(), ParseError> {  // jigsaw regions numbered from zero replace the boxes, numbers are kept
+        if regions.len() != self.content.len() {
+            return Err(ParseError::WrongLength { length: regions.len(), valid: vec!(self.content.len()) });
+        }
+        for region in 0..self.line_size {
+            let size = regions.iter().filter(|&&r| r == region).count();
+            if size != self.line_size {
+                return Err(ParseError::WrongRegionSize { region, size, expected: self.line_size });
+            }
+        }
+        self.change_houses(|b| b.regions = Some(Rc::from(regions)))
+    }
+
+    pub fn has_diagonals(&self) -> bool {
+        self.diagonals
+    }
+
+    pub fn set_diagonals(&mut self, diagonals: bool) -> Result<(), ParseError> {  // X-Sudoku, numbers are kept
+        self.change_houses(|b| b.diagonals = diagonals)
+    }
+
+    pub fn cages(&self) -> &[Cage] {
+        &self.cages
+    }
+
+    pub fn has_cages(&self) -> bool {
+        !self.cages.is_empty()
+    }
+
+    pub fn set_cages(&mut self, cages: Vec<Cage>) -> Result<(), ParseError> {  // killer cages replace the current ones, numbers are kept
+        let mut caged = vec!(false; self.content.len());
+        for (i, cage) in cages.iter().enumerate() {
+            let invalid = ParseError::InvalidCage { cage: i, size: cage.cells.len(), sum: cage.sum };
+            if cage.cells.iter().any(|&idx| idx >= self.content.len()) || Cage::combinations(self.line_size, cage.cells.len(), cage.sum).is_empty() {
+                return Err(invalid);
+            }
+            for &idx in cage.cells.iter() {
+                if caged[idx] {
+                    return Err(ParseError::OverlappingCages { cell: self.line_col_from_idx(idx) });
                 }
+                caged[idx] = true;
             }
         }
-        None
+        self.change_houses(|b| b.cages = Rc::from(cages))
     }
 
-    pub fn solve(self) -> Option<Self> {  // stack solver implementation
-        let mut stack: Vec<Board> = Vec::new();
-        stack.push(self);
-    
+    fn cage_of(&self, idx: usize) -> Option<usize> {
+        self.cages.iter().position(|cage| cage.cells.contains(&idx))
+    }
+
+    fn cage_options(&self, cage: usize) -> Option<Vec<(usize, Candidates)>> {  // the open cells of the cage with the options the sum combinations left allow, None if the numbers break the sum
+        let cells = &self.cages[cage].cells;
+        let placed: Vec<usize> = cells.iter().filter_map(|&idx| self.get_num_index(idx)).collect();
+        let open: Vec<usize> = cells.iter().copied().filter(|&idx| self.get_num_index(idx).is_none()).collect();
+        let placed_sum: usize = placed.iter().map(|n| n + 1).sum();
+        if placed_sum > self.cages[cage].sum || (open.is_empty() && placed_sum != self.cages[cage].sum) {
+            return None;
+        }
+
+        let lists: Vec<Candidates> = open.iter().map(|&idx| self.get_option_list(idx).unwrap_or_default()).collect();
+        let allowed = killer::options(self.line_size, self.cages[cage].sum, &placed, &lists);
+        if allowed.iter().any(|list| list.is_empty()) {
+            return None;
+        }
+        Some(open.into_iter().zip(allowed).collect())
+    }
+
+    fn restrict_cage(&mut self, cage: usize) -> Option<Vec<usize>> {  // narrow the option lists to the sum combinations left, the cells whose lists shrank, None if the numbers break the sum
+        let mut shrunk = Vec::new();
+        for (idx, allowed) in self.cage_options(cage)? {
+            if self.get_option_list(idx) != Some(allowed) {
+                self.content[idx] = Field::OptionList(allowed);
+                shrunk.push(idx);
+            }
+        }
+        Some(shrunk)
+    }
+
+    pub fn start(&self) -> Board {  // the puzzle as it was read or generated
+        let mut b = self.clone();
+        b.reset_to_start();
+        b
+    }
+
+    fn houses(&self) -> Vec<House> {
+        let mut houses = Vec::new();
+        houses.extend((0..self.line_size).map(House::Line));
+        houses.extend((0..self.line_size).map(House::Col));
+        houses.extend((0..self.line_size).map(House::Quad));
+        if self.diagonals {
+            houses.extend([House::Diagonal(0), House::Diagonal(1)]);
+        }
+        houses
+    }
+
+    fn house_cells(&self, house: House) -> Vec<usize> {
+        match house {
+            House::Line(line) => (0..self.line_size).map(|col| self.idx_from_line_col(line, col)).collect(),
+            House::Col(col) => (0..self.line_size).map(|line| self.idx_from_line_col(line, col)).collect(),
+            House::Diagonal(0) => (0..self.line_size).map(|i| self.idx_from_line_col(i, i)).collect(),
+            House::Diagonal(_) => (0..self.line_size).map(|i| self.idx_from_line_col(i, self.line_size - 1 - i)).collect(),
+            House::Quad(quad) => match &self.regions {
+                Some(regions) => (0..regions.len()).filter(|&idx| regions[idx] == quad).collect(),
+                None => {
+                    let (start_line, start_col) = self.quadrant_start_from_quad(quad);
+                    (0..self.line_size).map(|i| self.idx_from_line_col(start_line + i / self.shape.cols, start_col + i % self.shape.cols)).collect()
+                },
+            },
+        }
+    }
+
+    fn houses_of(&self, idx: usize) -> Vec<House> {
+        let (line, col) = self.line_col_from_idx(idx);
+        let mut houses = vec!(House::Line(line), House::Col(col), House::Quad(self.quad_of(line, col)));
+        if self.diagonals && line == col {
+            houses.push(House::Diagonal(0));
+        }
+        if self.diagonals && line + col == self.line_size - 1 {
+            houses.push(House::Diagonal(1));
+        }
+        houses
+    }
+
+    fn conflict(&self, idx: usize, val: usize) -> ParseError {  // error for a number that could not be set because a peer holds it or it breaks a cage sum
+        let peer = self.peers(idx).into_iter().find(|&p| self.get_num_index(p) == Some(val));
+        match (peer, self.cage_of(idx)) {
+            (None, Some(cage)) => ParseError::InvalidCage { cage, size: self.cages[cage].cells.len(), sum: self.cages[cage].sum },
+            (peer, _) => ParseError::ConflictingGivens { cell: self.line_col_from_idx(idx), peer: self.line_col_from_idx(peer.unwrap_or(idx)), value: val + 1 },
+        }
+    }
+
+    fn peers(&self, idx: usize) -> Vec<usize> {  // all other cells sharing a house or a cage with the cell
+        let mut peers = Vec::new();
+        let cage = self.cage_of(idx).map(|cage| self.cages[cage].cells.clone()).unwrap_or_default();
+        for c in self.houses_of(idx).into_iter().flat_map(|house| self.house_cells(house)).chain(cage) {
+            if c != idx && !peers.contains(&c) {
+                peers.push(c);
+            }
+        }
+        peers
+    }
+
+    fn sees(&self, a: usize, b: usize) -> bool {  // true if both cells are distinct and share a house or a cage
+        a != b && (self.houses_of(a).iter().any(|h| self.houses_of(b).contains(h)) || self.cage_of(a).is_some_and(|cage| self.cages[cage].cells.contains(&b)))
+    }
+
+    pub fn apply_step(&mut self, step: &Step) -> bool {  // false if the placement conflicts with the board
+        if let Some((idx, num)) = step.placement {
+            if !self.set_num_index(idx, num) {
+                return false;
+            }
+        }
+        for &(idx, num) in &step.eliminations {
+            self.remove_from_option_list(idx, num);
+        }
+        true
+    }
+
+    pub fn solve_logical(&self) -> LogicalSolve {  // solve by applying techniques only, every deduction is recorded
+        let mut board = self.clone();
+        let mut steps = Vec::new();
+        while let Some(step) = logic::find_step(&board) {
+            if !board.apply_step(&step) {
+                break;
+            }
+            steps.push(step);
+        }
+        LogicalSolve { board, steps }
+    }
+
+    pub fn hint(&self) -> Option<Step> {  // next logical deduction, None if solved or only guessing is left
+        logic::find_step(self)
+    }
+
+    pub fn grade(&self) -> Grade {  // difficulty by the hardest technique needed and its number of applications
+        grade::grade(&self.solve_logical())
+    }
+
+    pub fn solve(self) -> Option<Self> {  // a search lost in a hopeless branch starts over with other guesses and a larger budget
+        let mut budget = solutions::RESTART_BUDGET;
+        let mut restart = 0;
         loop {
-            let current_board = stack.pop();
-            if let None = current_board {
-                break None;
+            let mut solutions = Solutions::restarted(self.clone(), restart, budget);
+            match solutions.next() {
+                None if solutions.gave_up() => budget += budget / 2,
+                solution => return solution,
             }
-            let mut current_board = current_board.unwrap();
-    
-            let next_step = loop {
-                let mut cont = false;
-    
-                // line search
-                if let Some((num, index)) = current_board.find_single_option(
-                    |idx| current_board.line_iter(idx),
-                    |col, line| current_board.idx_from_line_col(line, col)
-                ) {
-                    cont = true;
-                    let res = current_board.set_num_index(index, num);
-                    if !res {
-                        break SolverStep::Unsolvable;
-                    }
+            restart += 1;
+        }
+    }
+
+    pub fn solve_with(self, solver: Solver) -> Option<Self> {
+        match solver {
+            Solver::Stack => self.solve(),
+            Solver::DancingLinks => dlx::solve(self),
+        }
+    }
+
+    pub fn solutions(&self) -> Solutions {  // lazy iterator over all solutions in depth-first order
+        Solutions::new(self.clone())
+    }
+
+    pub fn count_solutions(&self, limit: usize) -> usize {  // number of solutions, exploration stops once limit is reached
+        self.solutions().take(limit).count()
+    }
+
+    pub fn count_solutions_with(&self, solver: Solver, limit: usize) -> usize {
+        match solver {
+            Solver::Stack => self.count_solutions(limit),
+            Solver::DancingLinks => dlx::solutions(self.clone(), limit).len(),
+        }
+    }
+
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+
+    fn propagate(&mut self, queue: Vec<usize>) -> bool {  // apply naked and hidden singles in the houses of changed cells until nothing changes, false on contradiction
+        // every house is scanned once however many of its cells changed since its last scan
+        let mut propagation = Propagation {
+            queued_houses: vec!(false; 3 * self.line_size + 2),
+            queued_cages: vec!(false; self.cages.len()),
+            houses: Vec::new(),
+            cages: Vec::new(),
+        };
+        for idx in queue {
+            self.mark(idx, &mut propagation);
+        }
+
+        loop {
+            // sum combinations left in the cages of changed cells
+            if let Some(cage) = propagation.cages.pop() {
+                propagation.queued_cages[cage] = false;
+                match self.restrict_cage(cage) {
+                    Some(shrunk) => for c in shrunk {
+                        self.mark(c, &mut propagation);
+                    },
+                    None => return false,
                 }
-    
-                // column search
-                if let Some((num, index)) = current_board.find_single_option(
-                    |idx| current_board.col_iter(idx),
-                    |line, col| current_board.idx_from_line_col(line, col)
-                ) {
-                    cont = true;
-                    let res = current_board.set_num_index(index, num);
-                    if !res {
-                        break SolverStep::Unsolvable;
+                continue;
+            }
+
+            let Some(house) = propagation.houses.pop() else {
+                return true;
+            };
+            propagation.queued_houses[self.house_index(house)] = false;
+            let cells = self.house_cells(house);
+
+            // naked singles
+            for &c in cells.iter() {
+                if let Field::OptionList(list) = self.content[c] {
+                    if list.is_empty() || list.len() == 1 && !self.place(c, list.first().unwrap(), &mut propagation) {
+                        return false;
                     }
                 }
-    
-                // quadrant search
-                if let Some((num, index)) = current_board.find_single_option(
-                    |idx| current_board.quad_iter(idx),
-                    |intra_quadrant_idx, quadrant_idx| {
-                        let quad_line = intra_quadrant_idx / current_board.base;
-                        let quad_col = intra_quadrant_idx % current_board.base;
-    
-                        let quad_start_line = (quadrant_idx / current_board.base) * current_board.base;
-                        let quad_start_col = (quadrant_idx % current_board.base) * current_board.base;
-    
-                        current_board.idx_from_line_col(quad_start_line + quad_line, quad_start_col + quad_col)
-                    }
-                ) {
-                    cont = true;
-                    let res = current_board.set_num_index(index, num);
-                    if !res {
-                        break SolverStep::Unsolvable;
-                    }
+            }
+
+            // hidden singles
+            let mut placed = Candidates::empty();
+            let mut once = Candidates::empty();
+            let mut twice = Candidates::empty();
+            for &c in cells.iter() {
+                match self.content[c] {
+                    Field::Number(n) => placed.insert(n),
+                    Field::OptionList(list) => {
+                        twice = twice.union(once.intersection(list));
+                        once = once.union(list);
+                    },
                 }
-    
-                // find shortest option list
-                let res = current_board.iter().enumerate().min_by(|x, y|
-                    if let Field::OptionList(xl) = x.1 {
-                        if let Field::OptionList(yl) = y.1 {
-                            xl.len().cmp(&yl.len())
-                        } else {
-                            std::cmp::Ordering::Less
-                        }
-                    } else {
-                        std::cmp::Ordering::Greater
-                    }
-                );
-    
-                let mut shortest_list_idx = 0;
-                if let Some((idx, field)) = res {
-                    if let Field::OptionList(list) = field {
-                        match list.len() {
-                            0 => {
-                                break SolverStep::Unsolvable;
-                            },
-                            1 => {
-                                cont = true;
-                                let num = list[0];
-                                let res = current_board.set_num_index(idx, num);
-                                if !res {
-                                    break SolverStep::Unsolvable;
-                                }
-                            },
-                            _ => {
-                                shortest_list_idx = idx;
-                            },
-                        }
-                    }
+            }
+
+            // every number needs a place within the house
+            if placed.union(once) != Candidates::full(self.line_size) {
+                return false;
+            }
+
+            for num in once.difference(twice).difference(placed).iter() {
+                // the place is gone if it was already taken by another single of this house
+                let c = cells.iter().copied().find(|&c| self.get_option_list(c).is_some_and(|list| list.contains(num)));
+                match c {
+                    Some(c) if self.place(c, num, &mut propagation) => (),
+                    _ => return false,
                 }
-    
-                // check if all numbers set
-                if current_board.iter().all(|x| if let Field::Number(_) = x {true} else {false}) {
-                    break SolverStep::Solved;
+            }
+        }
+    }
+
+    fn mark(&self, idx: usize, propagation: &mut Propagation) {  // queue the houses and the cage of a changed cell
+        for house in self.houses_of(idx) {
+            let h = self.house_index(house);
+            if !propagation.queued_houses[h] {
+                propagation.queued_houses[h] = true;
+                propagation.houses.push(house);
+            }
+        }
+        if let Some(cage) = self.cage_of(idx) {
+            if !propagation.queued_cages[cage] {
+                propagation.queued_cages[cage] = true;
+                propagation.cages.push(cage);
+            }
+        }
+    }
+
+    fn house_index(&self, house: House) -> usize {  // position in Board::houses
+        match house {
+            House::Line(line) => line,
+            House::Col(col) => self.line_size + col,
+            House::Quad(quad) => 2 * self.line_size + quad,
+            House::Diagonal(d) => 3 * self.line_size + d,
+        }
+    }
+
+    fn place(&mut self, idx: usize, val: usize, propagation: &mut Propagation) -> bool {  // set a single and mark the cells it changed
+        match self.assign(idx, val) {
+            Some(changed) => {
+                for c in changed {
+                    self.mark(c, propagation);
                 }
-    
-                if !cont {
-                    break SolverStep::BranchOnOptionList(shortest_list_idx);
+                true
+            },
+            None => false,
+        }
+    }
+
+    fn assign(&mut self, idx: usize, val: usize) -> Option<Vec<usize>> {  // set a number, the cell and the cells losing the number as an option or sharing its cage, None on conflict
+        let mut changed = vec!(idx);
+        changed.extend(self.houses_of(idx).into_iter().flat_map(|house| self.house_cells(house))
+            .filter(|&c| c != idx && self.get_option_list(c).is_some_and(|list| list.contains(val))));
+        changed.extend(self.cage_of(idx).map(|cage| self.cages[cage].cells.clone()).unwrap_or_default());
+        if self.set_num_index(idx, val) { Some(changed) } else { None }
+    }
+
+    fn next_solver_step(&mut self, queue: Vec<usize>, restart: usize) -> SolverStep {  // propagate changes, then pick a field and a number to branch on
+        if !self.propagate(queue) {
+            return SolverStep::Unsolvable;
+        }
+
+        let Some(shortest) = self.content.iter().filter_map(|field| if let Field::OptionList(list) = field { Some(list.len()) } else { None }).min() else {
+            return SolverStep::Solved;
+        };
+        if shortest == 0 {
+            return SolverStep::Unsolvable;
+        }
+
+        // among the shortest option lists take the field with the most open fields in its houses,
+        // a guess there tells the most about the rest of the board
+        let mut open = vec!(0; 3 * self.line_size + 2);
+        for idx in 0..self.content.len() {
+            if self.get_num_index(idx).is_none() {
+                for house in self.houses_of(idx) {
+                    open[self.house_index(house)] += 1;
                 }
+            }
+        }
+        let idx = (0..self.content.len())
+            .filter(|&idx| self.get_option_list(idx).is_some_and(|list| list.len() == shortest))
+            .max_by_key(|&idx| (self.houses_of(idx).into_iter().map(|house| open[self.house_index(house)]).sum::<usize>(), Reverse(solutions::scramble(idx, restart))))
+            .unwrap();
+
+        // try the number first that the fewest peers still have as an option, it takes the least away from them
+        let peers = self.peers(idx);
+        let number = self.get_option_list(idx).unwrap().iter()
+            .min_by_key(|&n| (peers.iter().filter(|&&p| self.get_option_list(p).is_some_and(|list| list.contains(n))).count(), solutions::scramble(n, restart)))
+            .unwrap();
+        SolverStep::BranchOnOptionList(idx, number)
+    }
+
+    fn empty(shape: Shape) -> Board {
+        let mut b = Board::new();
+        b.reset(shape);
+        b
+    }
+
+    fn jigsaw(regions: &[usize]) -> Option<Board> {  // empty board with jigsaw regions, None if they are invalid
+        let line_size = (2..=regions.len()).find(|l| l * l == regions.len())?;
+        let mut b = Board::empty(Shape::for_line_size(line_size)?);
+        b.set_regions(regions).ok()?;
+        Some(b)
+    }
+
+    pub fn random_regions(shape: impl Into<Shape>) -> Vec<usize> {  // random jigsaw layout of connected regions, always solvable
+        Board::random_jigsaw(shape.into()).0.regions()
+    }
+
+    fn random_jigsaw(shape: Shape) -> (Board, Board) {  // empty board with random regions laid around a random solution, and that solution
+        // start with the boxes and a solution, swapping fields of the same number between neighbouring
+        // regions keeps every number once in every region, so the solution stays valid
+        let solution = Board::random_solution(&Board::empty(shape));
+        let mut regions = solution.regions();
+        let mut rng = rand::thread_rng();
+        for _ in 0..regions.len() * 8 {
+            let a = rng.gen_range(0..regions.len());
+            let Some(&n) = solution.neighbours(a).iter().filter(|&&n| regions[n] != regions[a]).collect::<Vec<_>>().choose(&mut rng).copied() else {
+                continue;
             };
-    
-            match next_step {
-                SolverStep::Solved => {
-                    break Some(current_board);
-                },
-                SolverStep::BranchOnOptionList(idx) => {
-                    let number = current_board.get_num_index(idx);
-                    if let Some(_) = number {
-                        break None;
-                    }
-    
-                    let number = current_board.get_first_option_list(idx);
-                    if let None = number {
-                        break None;
-                    }
-                    let number = number.unwrap();
-    
-                    let mut new_board = current_board.clone();
-    
-                    if !current_board.set_num_index(idx, number) {
-                        break None;
-                    }
-                    new_board.remove_from_option_list(idx, number);
-    
-                    stack.push(new_board);
-                    stack.push(current_board);
-                },
-                SolverStep::Unsolvable => (), // abandon this branch
+            let (from, to) = (regions[a], regions[n]);
+            let Some(c) = (0..regions.len()).find(|&c| regions[c] == to && solution.get_num_index(c) == solution.get_num_index(a)) else {
+                continue;
+            };
+
+            regions[a] = to;
+            regions[c] = from;
+            if !(solution.connected(&regions, from) && solution.connected(&regions, to)) {
+                regions[a] = from;
+                regions[c] = to;
+            }
+        }
+        // connected regions of line size fields are always valid
+        (Board::jigsaw(&regions).unwrap_or_else(|| Board::empty(shape)), solution)
+    }
+
+    fn diagonal(shape: Shape) -> Board {  // empty X-Sudoku board
+        let mut b = Board::empty(shape);
+        b.diagonals = true;
+        b
+    }
+
+    fn killer(shape: Shape) -> (Board, Board) {  // empty board with random cages cut from a random solution, and that solution
+        let solution = Board::random_solution(&Board::empty(shape));
+        let mut b = Board::empty(shape);
+        // cages of a solution always have a valid sum
+        let _ = b.set_cages(solution.random_cages());
+        (b, solution)
+    }
+
+    fn random_cages(&self) -> Vec<Cage> {  // connected cages of two to five fields covering a solved board, their sums are taken from it
+        let mut rng = rand::thread_rng();
+        let distinct = |cells: &[usize], n: usize| cells.iter().all(|&c| self.get_num_index(c) != self.get_num_index(n));
+        loop {
+            let mut owner: Vec<Option<usize>> = vec!(None; self.content.len());
+            let mut cages: Vec<Vec<usize>> = Vec::new();
+            for start in 0..self.content.len() {
+                if owner[start].is_some() {
+                    continue;
+                }
+                let size = rng.gen_range(2..=4);
+                let mut cells = vec!(start);
+                owner[start] = Some(cages.len());
+                while cells.len() < size {
+                    let options: Vec<usize> = cells.iter()
+                        .flat_map(|&c| self.neighbours(c))
+                        .filter(|&n| owner[n].is_none() && distinct(&cells, n))
+                        .collect();
+                    let Some(&n) = options.choose(&mut rng) else {
+                        break;
+                    };
+                    owner[n] = Some(cages.len());
+                    cells.push(n);
+                }
+                cages.push(cells);
+            }
+
+            // a cage of a single field would give its number away, it joins a neighbouring cage without that number
+            for cage in 0..cages.len() {
+                if cages[cage].len() != 1 {
+                    continue;
+                }
+                let cell = cages[cage][0];
+                let options: Vec<usize> = self.neighbours(cell).into_iter()
+                    .filter_map(|n| owner[n])
+                    .filter(|&c| c != cage && distinct(&cages[c], cell))
+                    .collect();
+                if let Some(&target) = options.choose(&mut rng) {
+                    cages[cage].clear();
+                    cages[target].push(cell);
+                    owner[cell] = Some(target);
+                }
+            }
+            if cages.iter().any(|cells| cells.len() == 1) {
+                continue;  // start over if a single field found no cage to join
             }
+
+            break cages.into_iter().filter(|cells| !cells.is_empty()).map(|mut cells| {
+                cells.sort();
+                let sum = cells.iter().filter_map(|&c| self.get_num_index(c)).map(|n| n + 1).sum();
+                Cage::new(cells, sum)
+            }).collect();
         }
     }
 
-    pub fn generate(base: usize) -> Board { // a board generator based on 5 random numbers from the number range of the board
+    fn neighbours(&self, idx: usize) -> Vec<usize> {  // fields left, right, above and below
+        let (line, col) = self.line_col_from_idx(idx);
+        let mut res = Vec::new();
+        if line > 0 { res.push(idx - self.line_size); }
+        if line + 1 < self.line_size { res.push(idx + self.line_size); }
+        if col > 0 { res.push(idx - 1); }
+        if col + 1 < self.line_size { res.push(idx + 1); }
+        res
+    }
+
+    fn connected(&self, regions: &[usize], region: usize) -> bool {  // true if the fields of region form one orthogonally connected area
+        let Some(start) = regions.iter().position(|&r| r == region) else {
+            return true;
+        };
+        let mut seen = vec!(start);
+        let mut todo = vec!(start);
+        while let Some(idx) = todo.pop() {
+            for n in self.neighbours(idx) {
+                if regions[n] == region && !seen.contains(&n) {
+                    seen.push(n);
+                    todo.push(n);
+                }
+            }
+        }
+        seen.len() == regions.iter().filter(|&&r| r == region).count()
+    }
+
+    fn random_solution(template: &Board) -> Board { // a solved board based on 5 random numbers from the number range of the board
+        if template.regions.is_some() {
+            // random numbers can leave jigsaw regions without a solution that takes long to prove,
+            // instead the template is filled by branching on random options. Early wrong guesses can
+            // take very long to undo, so the search restarts with budgets of 1, 1, 2, 1, 1, 2, 4, ...
+            // times the number of fields (Luby's sequence, generated by reluctant doubling)
+            let (mut u, mut v) = (1usize, 1usize);
+            loop {
+                let budget = v * template.content.len();
+                let mut solutions = Solutions::random(template.clone(), budget);
+                if let Some(solution) = solutions.next() {
+                    return solution;
+                }
+                if !solutions.gave_up() {
+                    return template.clone();
+                }
+                if u & u.wrapping_neg() == v {
+                    u += 1;
+                    v = 1;
+                } else {
+                    v *= 2;
+                }
+            }
+        }
+
         let numbers_count_max = 5;
-        let board_size = base.pow(4);
-        let num_to_delete = board_size * 100 / 70;
-    
-        let mut b = Board::new();
-        b.reset(base);
+        let board_size = template.content.len();
     
         let mut numbers_count = 0;
-        let mut solution = loop {
-            let mut current_board = b.clone();
+        loop {
+            let mut current_board = template.clone();
             loop {
                 let (idx, num) = loop {
                     let idx = rand::thread_rng().gen_range(0..board_size);
                     let list = current_board.get_option_list(idx);
                     if let Some(list) = list {
                         let list_idx = rand::thread_rng().gen_range(0..list.len());
-                        break (idx, list[list_idx]);
+                        break (idx, list.iter().nth(list_idx).unwrap());
                     }
                 };
     
@@ -507,13 +1263,123 @@ impl Board {
             if let Some(solution) = current_board.solve() {
                 break solution;
             }
-        };
+        }
+    }
+
+    fn from_clues(template: &Board, clues: &[Option<usize>]) -> Board {
+        let mut b = template.clone();
+        for (idx, clue) in clues.iter().enumerate() {
+            if let Some(num) = clue {
+                b.set_num_index(idx, *num);
+            }
+        }
+        b.fix_givens();
+        b
+    }
+
+    pub fn generate(shape: impl Into<Shape>) -> Board { // a board generator based on 5 random numbers from the number range of the board, a base or a Shape
+        let shape = shape.into();
+        let board_size = shape.line_size().pow(2);
+        let num_to_delete = board_size * 100 / 70;
+    
+        let mut solution = Board::random_solution(&Board::empty(shape));
     
         // delete numbers randomly
         for _ in 0..num_to_delete {
             solution.clear_num_index(rand::thread_rng().gen_range(0..board_size));
         }
 
+        solution.fix_givens();
         solution
     }
+
+    fn with_solution(template: Board) -> (Board, Board) {
+        let solution = Board::random_solution(&template);
+        (template, solution)
+    }
+
+    fn reduce<F>(template: &Board, solution: &Board, accept: F) -> Board  // delete numbers of solution in random order as long as the puzzle keeps a unique solution and is accepted
+    where
+        F: Fn(&Board) -> bool
+    {
+        let mut clues: Vec<Option<usize>> = (0..solution.content.len()).map(|idx| solution.get_num_index(idx)).collect();
+
+        let mut indices: Vec<usize> = (0..clues.len()).collect();
+        indices.shuffle(&mut rand::thread_rng());
+
+        // uniqueness checks of large boards may search for minutes, their clues are kept once the budget runs out
+        for idx in indices {
+            let clue = clues[idx].take();
+            let b = Board::from_clues(template, &clues);
+            let mut solutions = Solutions::limited(b.clone(), solutions::BUDGET);
+            if solutions.by_ref().take(2).count() != 1 || solutions.gave_up() || !accept(&b) {
+                clues[idx] = clue;
+            }
+        }
+
+        Board::from_clues(template, &clues)
+    }
+
+    pub fn generate_unique(shape: impl Into<Shape>) -> Board {  // clues are kept if their uniqueness check runs out of budget, large puzzles may keep a few more
+        let (template, solution) = Board::with_solution(Board::empty(shape.into()));
+        Board::reduce(&template, &solution, |_| true)
+    }
+
+    pub fn generate_with_difficulty(shape: impl Into<Shape>, band: RangeInclusive<Difficulty>) -> Board {  // unique puzzle whose grade lies within band
+        let shape = shape.into();
+        Board::reduce_to_band(band, || Board::with_solution(Board::empty(shape)))
+    }
+
+    pub fn generate_jigsaw(regions: &[usize]) -> Option<Board> {  // unique puzzle for a solvable jigsaw layout like random_regions, None if it is invalid
+        let (template, solution) = Board::with_solution(Board::jigsaw(regions)?);
+        Some(Board::reduce(&template, &solution, |_| true))
+    }
+
+    pub fn generate_jigsaw_with_difficulty(regions: &[usize], band: RangeInclusive<Difficulty>) -> Option<Board> {
+        let template = Board::jigsaw(regions)?;
+        Some(Board::reduce_to_band(band, || Board::with_solution(template.clone())))
+    }
+
+    pub fn generate_random_jigsaw(shape: impl Into<Shape>) -> Board {  // unique puzzle with random regions, the solution the regions are laid around is reused
+        let (template, solution) = Board::random_jigsaw(shape.into());
+        Board::reduce(&template, &solution, |_| true)
+    }
+
+    pub fn generate_random_jigsaw_with_difficulty(shape: impl Into<Shape>, band: RangeInclusive<Difficulty>) -> Board {
+        let shape = shape.into();
+        Board::reduce_to_band(band, || Board::random_jigsaw(shape))
+    }
+
+    pub fn generate_diagonal(shape: impl Into<Shape>) -> Board {  // unique X-Sudoku puzzle
+        let (template, solution) = Board::with_solution(Board::diagonal(shape.into()));
+        Board::reduce(&template, &solution, |_| true)
+    }
+
+    pub fn generate_diagonal_with_difficulty(shape: impl Into<Shape>, band: RangeInclusive<Difficulty>) -> Board {
+        let shape = shape.into();
+        Board::reduce_to_band(band, || Board::with_solution(Board::diagonal(shape)))
+    }
+
+    pub fn generate_killer(shape: impl Into<Shape>) -> Board {  // unique killer puzzle with random cages, the givens are reduced as far as possible, slow above 9x9
+        let (template, solution) = Board::killer(shape.into());
+        Board::reduce(&template, &solution, |_| true)
+    }
+
+    pub fn generate_killer_with_difficulty(shape: impl Into<Shape>, band: RangeInclusive<Difficulty>) -> Board {  // new cages are tried until the grade lies within band
+        let shape = shape.into();
+        Board::reduce_to_band(band, || Board::killer(shape))
+    }
+
+    fn reduce_to_band<F>(band: RangeInclusive<Difficulty>, puzzle: F) -> Board  // reduce new solutions of puzzle until the grade lies within band
+    where
+        F: Fn() -> (Board, Board)
+    {
+        loop {
+            let (template, solution) = puzzle();
+            let b = Board::reduce(&template, &solution, |b| b.grade().difficulty <= *band.end());
+            if band.contains(&b.grade().difficulty) {
+                break b;
+            }
+        }
+    }
 }
@@ -0,0 +1,494 @@
+use crate::Board;
+use crate::Candidates;
+use crate::House;
+use crate::killer;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Technique {  // ordered from easiest to hardest
+    NakedSingle,
+    HiddenSingle,
+    CageCombination,
+    PointingPair,
+    BoxLineReduction,
+    NakedPair,
+    XWing,
+    HiddenPair,
+    NakedTriple,
+    Swordfish,
+    HiddenTriple,
+    XYWing,
+    NakedQuad,
+    HiddenQuad,
+    SimpleColoring,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Step {  // values are zero-based just like the numbers stored in the board
+    pub technique: Technique,
+    pub cells: Vec<usize>,                  // cells forming the pattern
+    pub houses: Vec<House>,                 // houses the pattern is found in
+    pub placement: Option<(usize, usize)>,  // (cell, value) set by this step
+    pub eliminations: Vec<(usize, usize)>,  // (cell, value) removed from the option lists
+}
+
+pub struct LogicalSolve {
+    pub board: Board,
+    pub steps: Vec<Step>,
+}
+
+impl Technique {
+    pub const ALL: [Technique; 15] = [
+        Technique::NakedSingle,
+        Technique::HiddenSingle,
+        Technique::CageCombination,
+        Technique::PointingPair,
+        Technique::BoxLineReduction,
+        Technique::NakedPair,
+        Technique::XWing,
+        Technique::HiddenPair,
+        Technique::NakedTriple,
+        Technique::Swordfish,
+        Technique::HiddenTriple,
+        Technique::XYWing,
+        Technique::NakedQuad,
+        Technique::HiddenQuad,
+        Technique::SimpleColoring,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Technique::NakedSingle      => "Naked Single",
+            Technique::HiddenSingle     => "Hidden Single",
+            Technique::CageCombination  => "Cage Combination",
+            Technique::PointingPair     => "Pointing Pair",
+            Technique::BoxLineReduction => "Box/Line Reduction",
+            Technique::NakedPair        => "Naked Pair",
+            Technique::XWing            => "X-Wing",
+            Technique::HiddenPair       => "Hidden Pair",
+            Technique::NakedTriple      => "Naked Triple",
+            Technique::Swordfish        => "Swordfish",
+            Technique::HiddenTriple     => "Hidden Triple",
+            Technique::XYWing           => "XY-Wing",
+            Technique::NakedQuad        => "Naked Quad",
+            Technique::HiddenQuad       => "Hidden Quad",
+            Technique::SimpleColoring   => "Simple Coloring",
+        }
+    }
+
+    pub fn rating(&self) -> f32 {  // difficulty in the spirit of the Sudoku Explainer rating
+        match self {
+            Technique::NakedSingle      => 1.2,
+            Technique::HiddenSingle     => 1.5,
+            Technique::CageCombination  => 2.0,
+            Technique::PointingPair     => 2.6,
+            Technique::BoxLineReduction => 2.8,
+            Technique::NakedPair        => 3.0,
+            Technique::XWing            => 3.2,
+            Technique::HiddenPair       => 3.4,
+            Technique::NakedTriple      => 3.6,
+            Technique::Swordfish        => 3.8,
+            Technique::HiddenTriple     => 4.0,
+            Technique::XYWing           => 4.2,
+            Technique::NakedQuad        => 5.0,
+            Technique::HiddenQuad       => 5.4,
+            Technique::SimpleColoring   => 5.6,
+        }
+    }
+
+    fn find(&self, b: &Board) -> Option<Step> {
+        match self {
+            Technique::NakedSingle      => naked_single(b),
+            Technique::HiddenSingle     => hidden_single(b),
+            Technique::CageCombination  => cage_combination(b),
+            Technique::PointingPair     => pointing(b),
+            Technique::BoxLineReduction => box_line_reduction(b),
+            Technique::NakedPair        => naked_subset(b, 2, *self),
+            Technique::XWing            => fish(b, 2, *self),
+            Technique::HiddenPair       => hidden_subset(b, 2, *self),
+            Technique::NakedTriple      => naked_subset(b, 3, *self),
+            Technique::Swordfish        => fish(b, 3, *self),
+            Technique::HiddenTriple     => hidden_subset(b, 3, *self),
+            Technique::XYWing           => xy_wing(b),
+            Technique::NakedQuad        => naked_subset(b, 4, *self),
+            Technique::HiddenQuad       => hidden_subset(b, 4, *self),
+            Technique::SimpleColoring   => simple_coloring(b),
+        }
+    }
+}
+
+impl Step {
+    pub fn changed(&self) -> Vec<usize> {  // cells whose number or options are changed by this step
+        let mut cells: Vec<usize> = self.placement.iter().chain(self.eliminations.iter()).map(|&(idx, _)| idx).collect();
+        cells.sort();
+        cells.dedup();
+        cells
+    }
+
+    pub fn describe(&self, b: &Board) -> String {  // e.g. "Naked Single: r3c5=7" or "Pointing Pair: r1c4-5, r2c4-5"
+        let cell = |idx: usize| {
+            let (line, col) = b.line_col_from_idx(idx);
+            format!("r{}c{}", line + 1, col + 1)
+        };
+        let mut changes: Vec<String> = self.placement.iter().map(|&(idx, val)| format!("{}={}", cell(idx), val + 1)).collect();
+        changes.extend(self.eliminations.iter().map(|&(idx, val)| format!("{}-{}", cell(idx), val + 1)));
+        format!("{}: {}", self.technique.name(), changes.join(", "))
+    }
+}
+
+impl LogicalSolve {
+    pub fn is_solved(&self) -> bool {
+        self.board.iter().all(|x| matches!(x, crate::Field::Number(_)))
+    }
+}
+
+pub fn find_step(b: &Board) -> Option<Step> {  // the deduction of the easiest technique that makes progress
+    Technique::ALL.iter().find_map(|t| t.find(b))
+}
+
+fn candidates(b: &Board, idx: usize) -> Candidates {
+    b.get_option_list(idx).unwrap_or_default()
+}
+
+fn cells_with(b: &Board, cells: &[usize], val: usize) -> Vec<usize> {
+    cells.iter().copied().filter(|&idx| candidates(b, idx).contains(val)).collect()
+}
+
+fn combinations(items: &[usize], k: usize) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return vec!(Vec::new());
+    }
+    let mut res = Vec::new();
+    for i in 0..items.len() {
+        for mut rest in combinations(&items[(i + 1)..], k - 1) {
+            rest.insert(0, items[i]);
+            res.push(rest);
+        }
+    }
+    res
+}
+
+fn union(lists: &[&[usize]]) -> Vec<usize> {
+    let mut res: Vec<usize> = Vec::new();
+    for list in lists {
+        for x in list.iter() {
+            if !res.contains(x) {
+                res.push(*x);
+            }
+        }
+    }
+    res.sort();
+    res
+}
+
+fn elimination_step(technique: Technique, cells: Vec<usize>, houses: Vec<House>, eliminations: Vec<(usize, usize)>) -> Option<Step> {
+    if eliminations.is_empty() {
+        None
+    } else {
+        Some(Step { technique, cells, houses, placement: None, eliminations })
+    }
+}
+
+fn naked_single(b: &Board) -> Option<Step> {
+    (0..b.content.len()).find_map(|idx| {
+        let list = candidates(b, idx);
+        if list.len() == 1 {
+            Some(Step { technique: Technique::NakedSingle, cells: vec!(idx), houses: b.houses_of(idx), placement: Some((idx, list.first().unwrap())), eliminations: Vec::new() })
+        } else {
+            None
+        }
+    })
+}
+
+fn hidden_single(b: &Board) -> Option<Step> {
+    for house in b.houses() {
+        let cells = b.house_cells(house);
+        for val in 0..b.line_size {
+            let found = cells_with(b, &cells, val);
+            if found.len() == 1 {
+                return Some(Step { technique: Technique::HiddenSingle, cells: found.clone(), houses: vec!(house), placement: Some((found[0], val)), eliminations: Vec::new() });
+            }
+        }
+    }
+    None
+}
+
+fn cage_combination(b: &Board) -> Option<Step> {  // options of a killer cage that are part of no sum combination left
+    for cage in b.cages() {
+        let placed: Vec<usize> = cage.cells.iter().filter_map(|&idx| b.get_num_index(idx)).collect();
+        let open: Vec<usize> = cage.cells.iter().copied().filter(|&idx| b.get_num_index(idx).is_none()).collect();
+        let lists: Vec<Candidates> = open.iter().map(|&idx| candidates(b, idx)).collect();
+        let allowed = killer::options(b.line_size, cage.sum, &placed, &lists);
+
+        let mut eliminations = Vec::new();
+        for (i, &idx) in open.iter().enumerate() {
+            eliminations.extend(lists[i].difference(allowed[i]).iter().map(|val| (idx, val)));
+        }
+
+        let step = elimination_step(Technique::CageCombination, cage.cells.clone(), Vec::new(), eliminations);
+        if step.is_some() {
+            return step;
+        }
+    }
+    None
+}
+
+fn naked_subset(b: &Board, size: usize, technique: Technique) -> Option<Step> {  // size cells in a house sharing exactly size options
+    for house in b.houses() {
+        let cells = b.house_cells(house);
+        let open: Vec<usize> = cells.iter().copied().filter(|&idx| (2..=size).contains(&candidates(b, idx).len())).collect();
+        for subset in combinations(&open, size) {
+            let values = subset.iter().fold(Candidates::empty(), |acc, &idx| acc.union(candidates(b, idx)));
+            if values.len() != size {
+                continue;
+            }
+
+            let mut eliminations = Vec::new();
+            for &idx in cells.iter().filter(|idx| !subset.contains(idx)) {
+                for val in values.intersection(candidates(b, idx)).iter() {
+                    eliminations.push((idx, val));
+                }
+            }
+
+            let step = elimination_step(technique, subset, vec!(house), eliminations);
+            if step.is_some() {
+                return step;
+            }
+        }
+    }
+    None
+}
+
+fn hidden_subset(b: &Board, size: usize, technique: Technique) -> Option<Step> {  // size values in a house restricted to exactly size cells
+    for house in b.houses() {
+        let cells = b.house_cells(house);
+        let values: Vec<usize> = (0..b.line_size).filter(|&val| (1..=size).contains(&cells_with(b, &cells, val).len())).collect();
+        for subset in combinations(&values, size) {
+            let positions: Vec<Vec<usize>> = subset.iter().map(|&val| cells_with(b, &cells, val)).collect();
+            let lists: Vec<&[usize]> = positions.iter().map(|p| p.as_slice()).collect();
+            let found = union(&lists);
+            if found.len() != size {
+                continue;
+            }
+
+            let mut eliminations = Vec::new();
+            for &idx in found.iter() {
+                for val in candidates(b, idx).iter().filter(|val| !subset.contains(val)) {
+                    eliminations.push((idx, val));
+                }
+            }
+
+            let step = elimination_step(technique, found, vec!(house), eliminations);
+            if step.is_some() {
+                return step;
+            }
+        }
+    }
+    None
+}
+
+fn pointing(b: &Board) -> Option<Step> {  // options of a quadrant confined to one line/col remove the value from the rest of it
+    for quad in (0..b.line_size).map(House::Quad) {
+        let cells = b.house_cells(quad);
+        for val in 0..b.line_size {
+            let found = cells_with(b, &cells, val);
+            if found.len() < 2 {
+                continue;
+            }
+
+            let (line, col) = b.line_col_from_idx(found[0]);
+            for house in [House::Line(line), House::Col(col)] {
+                let house_cells = b.house_cells(house);
+                if !found.iter().all(|idx| house_cells.contains(idx)) {
+                    continue;
+                }
+
+                let eliminations: Vec<(usize, usize)> = cells_with(b, &house_cells, val).into_iter()
+                    .filter(|idx| !cells.contains(idx))
+                    .map(|idx| (idx, val))
+                    .collect();
+                let step = elimination_step(Technique::PointingPair, found.clone(), vec!(quad, house), eliminations);
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+    None
+}
+
+fn box_line_reduction(b: &Board) -> Option<Step> {  // options of a line/col confined to one quadrant remove the value from the rest of it
+    let lines = (0..b.line_size).map(House::Line);
+    let cols = (0..b.line_size).map(House::Col);
+    for house in lines.chain(cols) {
+        let cells = b.house_cells(house);
+        for val in 0..b.line_size {
+            let found = cells_with(b, &cells, val);
+            if found.len() < 2 {
+                continue;
+            }
+
+            let quad = b.houses_of(found[0])[2];
+            let quad_cells = b.house_cells(quad);
+            if !found.iter().all(|idx| quad_cells.contains(idx)) {
+                continue;
+            }
+
+            let eliminations: Vec<(usize, usize)> = cells_with(b, &quad_cells, val).into_iter()
+                .filter(|idx| !cells.contains(idx))
+                .map(|idx| (idx, val))
+                .collect();
+            let step = elimination_step(Technique::BoxLineReduction, found, vec!(house, quad), eliminations);
+            if step.is_some() {
+                return step;
+            }
+        }
+    }
+    None
+}
+
+fn fish(b: &Board, size: usize, technique: Technique) -> Option<Step> {  // X-Wing (size 2) and Swordfish (size 3)
+    for val in 0..b.line_size {
+        for by_line in [true, false] {
+            let base_house = |i| if by_line { House::Line(i) } else { House::Col(i) };
+            let cover_house = |i| if by_line { House::Col(i) } else { House::Line(i) };
+            let cover_index = |idx| {
+                let (line, col) = b.line_col_from_idx(idx);
+                if by_line { col } else { line }
+            };
+
+            let bases: Vec<usize> = (0..b.line_size)
+                .filter(|&i| (2..=size).contains(&cells_with(b, &b.house_cells(base_house(i)), val).len()))
+                .collect();
+            for subset in combinations(&bases, size) {
+                let mut found = Vec::new();
+                for &i in subset.iter() {
+                    found.extend(cells_with(b, &b.house_cells(base_house(i)), val));
+                }
+                let covers: Vec<usize> = union(&[&found.iter().map(|&idx| cover_index(idx)).collect::<Vec<usize>>()]);
+                if covers.len() != size {
+                    continue;
+                }
+
+                let mut eliminations = Vec::new();
+                for &c in covers.iter() {
+                    for idx in cells_with(b, &b.house_cells(cover_house(c)), val) {
+                        if !found.contains(&idx) {
+                            eliminations.push((idx, val));
+                        }
+                    }
+                }
+
+                let houses = subset.iter().map(|&i| base_house(i)).chain(covers.iter().map(|&c| cover_house(c))).collect();
+                let step = elimination_step(technique, found, houses, eliminations);
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+    None
+}
+
+fn xy_wing(b: &Board) -> Option<Step> {  // pivot {x, y} seeing pincers {x, z} and {y, z}: z is removed from cells seeing both pincers
+    let bivalue: Vec<usize> = (0..b.content.len()).filter(|&idx| candidates(b, idx).len() == 2).collect();
+    for &pivot in bivalue.iter() {
+        let xy = candidates(b, pivot);
+        for &p1 in bivalue.iter().filter(|&&p| b.sees(pivot, p)) {
+            let xz = candidates(b, p1);
+            if xz.intersection(xy).len() != 1 {
+                continue;
+            }
+            let z = xz.difference(xy);
+            let yz = xy.difference(xz).union(z);
+            let z = z.first().unwrap();
+
+            for &p2 in bivalue.iter().filter(|&&p| p != p1 && b.sees(pivot, p)) {
+                if candidates(b, p2) != yz {
+                    continue;
+                }
+
+                let eliminations: Vec<(usize, usize)> = (0..b.content.len())
+                    .filter(|&idx| idx != pivot && b.sees(idx, p1) && b.sees(idx, p2) && candidates(b, idx).contains(z))
+                    .map(|idx| (idx, z))
+                    .collect();
+                let houses = common_houses(b, pivot, p1).into_iter().chain(common_houses(b, pivot, p2)).collect();
+                let step = elimination_step(Technique::XYWing, vec!(pivot, p1, p2), houses, eliminations);
+                if step.is_some() {
+                    return step;
+                }
+            }
+        }
+    }
+    None
+}
+
+fn common_houses(b: &Board, p: usize, q: usize) -> Vec<House> {
+    let houses = b.houses_of(q);
+    b.houses_of(p).into_iter().filter(|h| houses.contains(h)).collect()
+}
+
+fn simple_coloring(b: &Board) -> Option<Step> {  // two-color chains of conjugate pairs of a single value
+    for val in 0..b.line_size {
+        let mut links: Vec<(usize, usize, House)> = Vec::new();
+        for house in b.houses() {
+            let found = cells_with(b, &b.house_cells(house), val);
+            if found.len() == 2 {
+                links.push((found[0], found[1], house));
+            }
+        }
+
+        let mut color: Vec<Option<bool>> = vec!(None; b.content.len());
+        for &(start, _, _) in links.iter() {
+            if color[start].is_some() {
+                continue;
+            }
+
+            // color the chain connected to the start cell
+            let mut chain = vec!(start);
+            let mut houses = Vec::new();
+            color[start] = Some(true);
+            let mut pos = 0;
+            while pos < chain.len() {
+                let idx = chain[pos];
+                pos += 1;
+                for &(p, q, house) in links.iter() {
+                    let next = if p == idx { q } else if q == idx { p } else { continue };
+                    if !houses.contains(&house) {
+                        houses.push(house);
+                    }
+                    if color[next].is_none() {
+                        color[next] = Some(!color[idx].unwrap());
+                        chain.push(next);
+                    }
+                }
+            }
+            if chain.len() < 3 {
+                continue;
+            }
+
+            // color wrap: two cells of the same color see each other, so this color is false
+            let wrapped = [true, false].into_iter().find(|&c| {
+                chain.iter().any(|&p| color[p] == Some(c) && chain.iter().any(|&q| color[q] == Some(c) && b.sees(p, q)))
+            });
+            let eliminations: Vec<(usize, usize)> = if let Some(c) = wrapped {
+                chain.iter().filter(|&&idx| color[idx] == Some(c)).map(|&idx| (idx, val)).collect()
+            } else {
+                // color trap: a cell seeing both colors can not hold the value
+                (0..b.content.len())
+                    .filter(|&idx| !chain.contains(&idx) && candidates(b, idx).contains(val))
+                    .filter(|&idx| {
+                        chain.iter().any(|&p| color[p] == Some(true) && b.sees(idx, p)) &&
+                        chain.iter().any(|&p| color[p] == Some(false) && b.sees(idx, p))
+                    })
+                    .map(|idx| (idx, val))
+                    .collect()
+            };
+
+            let step = elimination_step(Technique::SimpleColoring, chain, houses, eliminations);
+            if step.is_some() {
+                return step;
+            }
+        }
+    }
+    None
+}
@@ -1,30 +1,101 @@
 use sudoku::Board;
+use sudoku::Colors;
+use sudoku::Difficulty;
+use sudoku::Encoding;
+use sudoku::Format;
+use sudoku::GridReader;
+use sudoku::Metadata;
+use sudoku::MultiBoard;
+use sudoku::MultiLayout;
+use sudoku::ParseError;
+use sudoku::RenderOptions;
+use sudoku::Shape;
+use sudoku::Solver;
 
 fn print_help() {
     println!("Sudoku generator and solver");
     println!("Usage:");
     println!("        sudoku [Options]");
-    println!("");
-    println!("    -s :                  Solve puzzles from stdin. Board size is determined from input string.");
-    println!("    -b :    -b <Base>     Set base for puzzle generation (2-9).");
+    println!();
+    println!("    -s :                  Solve puzzles from stdin. Board size and encoding are determined from input string.");
+    println!("                          Puzzles may also be given as multi-line grids.");
+    println!("                          Candidate strings (729 characters for 9x9) continue from their pencil marks.");
+    println!("    -P :                  Read one-line puzzles from stdin as candidate strings, needed for 4x4 boards");
+    println!("                          whose 64 characters are also read as 8x8 puzzles.");
+    println!("    -x :                  Solve with the Dancing Links (exact cover) solver.");
+    println!("    -t :                  Trace the logical solver step by step for puzzles from stdin.");
+    println!("    -g :                  Grade puzzles from stdin and print score and difficulty.");
+    println!("    -b :    -b <Base>     Set base for puzzle generation (2-9), or box shape");
+    println!("                          like 2x3, 2x4, 2x5 or 3x4 (lines x columns of a box).");
     println!("    -n :    -n <Number>   Generate n puzzles.");
+    println!("    -u :                  Generate puzzles with a unique solution. Boards above 16x16 take up to");
+    println!("                          a few minutes each, progress is shown on stderr.");
+    println!("    -X :                  X-Sudoku, both main diagonals hold every number once. Applies to");
+    println!("                          puzzles from stdin and generates unique puzzles.");
+    println!("    -j :                  Generate unique jigsaw puzzles with random regions, written in the");
+    println!("                          jigsaw format unless pretty printed, rendered or another format is set.");
+    println!("    -k :                  Generate unique killer puzzles with random cages, written in the");
+    println!("                          killer format unless pretty printed, rendered or another format is set.");
+    println!("    -M :    -M <Layout>   Overlapping multi-grid puzzles (samurai, twin, butterfly). Solves them from");
+    println!("                          stdin with -s, puzzles are separated by empty lines, or generates unique puzzles.");
+    println!("    -d :    -d <Min[-Max]> Generate unique puzzles within a difficulty band");
+    println!("                          (easy, medium, hard, expert, diabolical).");
     println!("    -p :                  Pretty print puzzles instead of one-line output.");
+    println!("    -e :    -e <Encoding> Output encoding for boards larger than 9x9");
+    println!("                          (numbers, zero-based, one-based, letters), also reads input");
+    println!("                          whose symbols fit zero-based and one-based numbers alike.");
+    println!("    -r :                  Render puzzles with Unicode box drawing.");
+    println!("    -c :                  Render candidates inside empty fields.");
+    println!("    -l :                  Render row and column labels.");
+    println!("    -w :    -w <Char>     Render empty fields and missing candidates with this character.");
+    println!("    -m :    -m <Colors>   Color output (auto, always, never), auto colors terminals only.");
+    println!("    -f :    -f <Format>   Read and write puzzles in a file format (sdk, sdm, ss, jigsaw, killer).");
+    println!("                          Grading stores the difficulty in the level of .sdk, .jigsaw and .killer files.");
     println!("    -h :                  Print help.");
 }
 
+fn show(b: &Board, render: bool, pretty_print: bool, options: &RenderOptions) -> String {
+    if render {
+        b.render(options)
+    } else {
+        b.print_colored(pretty_print, options)
+    }
+}
+
 fn main() {
     let mut solve = false;
+    let mut grade = false;
+    let mut solver = Solver::Stack;
     let mut pretty_print = false;
-    let mut base = 3;
+    let mut render = false;
+    let mut options = RenderOptions { colors: Colors::Auto, ..RenderOptions::default() };
+    let mut trace = false;
+    let mut pencil_marks = false;
+    let mut encoding = Encoding::Numbers;
+    let mut explicit_encoding = false;
+    let mut format = None;
+    let mut unique = false;
+    let mut jigsaw = false;
+    let mut killer = false;
+    let mut multi = None;
+    let mut diagonals = false;
+    let mut band = None;
+    let mut shape = Shape::square(3);
     let mut num = 1;
 
     let args : Vec<String> = std::env::args().collect();
     let mut set_base = false;
     let mut set_number = false;
+    let mut set_difficulty = false;
+    let mut set_encoding = false;
+    let mut set_format = false;
+    let mut set_blank = false;
+    let mut set_colors = false;
+    let mut set_multi = false;
     for arg in args {
         if set_base {
-            if let Ok(n) = arg.parse::<usize>() {
-                base = n;
+            if let Some(s) = Shape::from_name(&arg) {
+                shape = s;
             }
         }
 
@@ -34,14 +105,71 @@ fn main() {
             }
         }
 
+        if set_difficulty {
+            let (min, max) = arg.split_once('-').unwrap_or((&arg, &arg));
+            if let (Some(min), Some(max)) = (Difficulty::from_name(min), Difficulty::from_name(max)) {
+                band = Some(min..=max);
+            }
+        }
+
+        if set_encoding {
+            if let Some(e) = Encoding::from_name(&arg) {
+                encoding = e;
+                explicit_encoding = true;
+            }
+        }
+
+        if set_format {
+            format = Format::from_name(&arg);
+        }
+
+        if set_blank {
+            if let Some(c) = arg.chars().next() {
+                options.blank = c;
+            }
+        }
+
+        if set_colors {
+            if let Some(c) = Colors::from_name(&arg) {
+                options.colors = c;
+            }
+        }
+
+        if set_multi {
+            multi = MultiLayout::from_name(&arg);
+        }
+
         set_base = false;
         set_number = false;
+        set_difficulty = false;
+        set_encoding = false;
+        set_format = false;
+        set_blank = false;
+        set_colors = false;
+        set_multi = false;
 
         match arg.as_str() {
             "-s" => solve = true,
+            "-x" => solver = Solver::DancingLinks,
+            "-t" => trace = true,
+            "-P" => pencil_marks = true,
+            "-g" => grade = true,
             "-b" => set_base = true,
             "-n" => set_number = true,
+            "-d" => set_difficulty = true,
+            "-u" => unique = true,
+            "-j" => jigsaw = true,
+            "-k" => killer = true,
+            "-M" => set_multi = true,
+            "-X" => diagonals = true,
             "-p" => pretty_print = true,
+            "-r" => render = true,
+            "-c" => (render, options.candidates) = (true, true),
+            "-l" => (render, options.labels) = (true, true),
+            "-w" => (render, set_blank) = (true, true),
+            "-m" => set_colors = true,
+            "-e" => set_encoding = true,
+            "-f" => set_format = true,
             "-h" => {
                 print_help();
                 return;
@@ -50,8 +178,64 @@ fn main() {
         }
     }
 
-    if solve {
-        // solve sudokus from stdin
+    options.encoding = encoding;
+
+    if let (Some(layout), true) = (multi, solve) {
+        // composite puzzles from stdin, each one is followed by an empty line
+        let input = std::io::read_to_string(std::io::stdin()).unwrap_or_default();
+        let mut puzzle = String::new();
+        for line in input.lines().chain([""]) {
+            if !line.trim().is_empty() {
+                puzzle.push_str(line);
+                puzzle.push('\n');
+                continue;
+            }
+            if puzzle.is_empty() {
+                continue;
+            }
+
+            let mut b = MultiBoard::new(layout);
+            match b.read(&puzzle).map(|_| b.solve()) {
+                Ok(Some(solution)) => println!("{}", solution.print()),
+                Ok(None) => println!("Could not find solution!"),
+                Err(e) => println!("Error: {}.", e),
+            }
+            puzzle.clear();
+        }
+    } else if let Some(layout) = multi.filter(|_| !(solve || grade || trace)) {
+        // generate composite puzzles, each one is followed by an empty line
+        for _ in 0..num {
+            println!("{}", MultiBoard::generate(layout).print());
+        }
+    } else if let (Some(format), true) = (format, solve || grade) {
+        // solve or grade puzzles of a file from stdin, metadata is passed through
+        let input = std::io::read_to_string(std::io::stdin()).unwrap_or_default();
+        for puzzle in format.puzzles(&input) {
+            let mut b = Board::new();
+            let read = b.read_format(puzzle, format);
+            let mut metadata = match read.and_then(|m| if diagonals { b.set_diagonals(true).map(|_| m) } else { Ok(m) }) {
+                Ok(m) => m,
+                Err(e) => {
+                    println!("Error: {}.", e);
+                    continue;
+                },
+            };
+
+            if grade {
+                metadata.set(Metadata::LEVEL, b.grade().difficulty.name());
+                print!("{}", b.print_format(format, &metadata));
+                continue;
+            }
+
+            if let Some(board) = b.solve_with(solver) {
+                print!("{}", board.print_format(format, &metadata));
+            } else {
+                println!("Could not find solution!");
+            }
+        }
+    } else if solve || grade || trace {
+        // solve or grade sudokus from stdin
+        let mut grid = GridReader::new();
         loop {
             let mut puzzle = String::new();
             let res = std::io::stdin().read_line(&mut puzzle);
@@ -61,25 +245,114 @@ fn main() {
                 _      => (),
             }
 
+            // one-line puzzles, lines of other lengths are collected as rows of a grid
             let mut b = Board::new();
-            if !b.read(puzzle.trim()) {
-                println!("Error: can not read board.");
+            let mut res = Err(ParseError::WrongLength { length: 0, valid: Vec::new() });
+            let in_grid = !grid.is_empty();
+            if !in_grid && pencil_marks {
+                res = b.read_pencil_marks(puzzle.trim());
+            } else if !in_grid {
+                res = b.read(puzzle.trim());
+                if explicit_encoding && matches!(res, Err(ParseError::AmbiguousEncoding { .. })) {
+                    res = b.read_with(puzzle.trim(), encoding);
+                }
+                if let Err(ParseError::WrongLength { .. }) = res {
+                    if b.read_pencil_marks(puzzle.trim()).is_ok() {
+                        res = Ok(());
+                    }
+                }
+            }
+            if let Err(ParseError::WrongLength { .. }) = res {
+                match grid.push_line(&puzzle) {
+                    Ok(None) => continue,
+                    Ok(Some(text)) => res = b.read_grid(&text),
+                    Err(e) if in_grid => res = Err(e),
+                    Err(_) => (),  // neither a one-line puzzle nor the first row of a grid
+                }
+            }
+
+            if diagonals && res.is_ok() {
+                res = b.set_diagonals(true);
+            }
+            if let Err(e) = res {
+                println!("Error: {}.", e);
+                continue;
+            }
+
+            if trace {
+                // every step with the board after it, the changed fields are highlighted
+                let logical = b.solve_logical();
+                let mut board = b.clone();
+                for (i, step) in logical.steps.iter().enumerate() {
+                    board.apply_step(step);
+                    let options = RenderOptions { changed: step.changed(), ..options.clone() };
+                    println!("{}. {}", i + 1, step.describe(&board));
+                    print!("{}", show(&board, render, pretty_print, &options));
+                }
+                if !logical.is_solved() {
+                    println!("No further logical step, guessing is needed.");
+                }
+                continue;
+            }
+
+            if grade {
+                let grade = b.grade();
+                let separator = if render { "\n" } else { " " };  // rendered boards start on a line of their own
+                print!("{:.2} {}{}{}", grade.score, grade.difficulty.name(), separator, show(&b, render, pretty_print, &options));
                 continue;
             }
 
-            let solution = b.solve();
+            let solution = b.solve_with(solver);
 
             if let Some(board) = solution {
-                print!("{}", board.print(pretty_print));
+                print!("{}", show(&board, render, pretty_print, &options));
             } else {
                 println!("Could not find solution!");
             }
         }
+
+        if let Err(e) = grid.finish() {
+            println!("Error: {}.", e);
+        }
     } else {
         // generate sudokus
-        for _ in 0..num {
-            let b = Board::generate(base);
-            print!("{}", b.print(pretty_print));
+        if jigsaw && format.is_none() && !(render || pretty_print) {
+            format = Some(Format::Jigsaw);
+        } else if killer && format.is_none() && !(render || pretty_print) {
+            format = Some(Format::Killer);
+        }
+        // reducing unique puzzles above 16x16 takes up to a few minutes, stderr keeps the output clean
+        let slow = (unique || jigsaw || killer || diagonals || band.is_some()) && shape.line_size() > 16;
+        for i in 0..num {
+            if slow {
+                eprintln!("Generating {0}x{0} puzzle {1} of {2}, this may take a few minutes...", shape.line_size(), i + 1, num);
+            }
+            let b = if jigsaw {
+                match &band {
+                    Some(band) => Board::generate_random_jigsaw_with_difficulty(shape, band.clone()),
+                    None => Board::generate_random_jigsaw(shape),
+                }
+            } else if killer {
+                match &band {
+                    Some(band) => Board::generate_killer_with_difficulty(shape, band.clone()),
+                    None => Board::generate_killer(shape),
+                }
+            } else if diagonals {
+                match &band {
+                    Some(band) => Board::generate_diagonal_with_difficulty(shape, band.clone()),
+                    None => Board::generate_diagonal(shape),
+                }
+            } else if let Some(band) = &band {
+                Board::generate_with_difficulty(shape, band.clone())
+            } else if unique {
+                Board::generate_unique(shape)
+            } else {
+                Board::generate(shape)
+            };
+            match format {
+                Some(format) => print!("{}", b.print_format(format, &Metadata::new())),
+                None => print!("{}", show(&b, render, pretty_print, &options)),
+            }
         }
     }
 }
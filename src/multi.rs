@@ -0,0 +1,339 @@
+use rand::Rng;
+use rand::seq::SliceRandom;
+
+use crate::Board;
+use crate::Candidates;
+use crate::Field;
+use crate::ParseError;
+use crate::Shape;
+use crate::solutions::BUDGET;
+
+// Composite puzzles of several 9x9 grids sharing boxes, laid out on a square canvas of fields:
+// Samurai: five grids, the corner boxes of the centre grid belong to the four corner grids as well (21x21)
+// Twin: two grids, the lower right box of the first is the upper left box of the second (15x15)
+// Butterfly: four grids overlapping in a 12x12 square, neighbouring grids share six boxes
+// The text format has one character per field of the canvas, '.' or '0' for empty fields and spaces
+// outside of the grids, trailing spaces may be left out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MultiLayout {
+    Samurai,
+    Twin,
+    Butterfly,
+}
+
+#[derive(Clone)]
+pub struct MultiBoard {
+    layout: MultiLayout,
+    grids: Vec<Board>,  // in the order of the offsets of the layout
+}
+
+pub struct MultiSolutions {
+    stack: Vec<(MultiBoard, Vec<Vec<usize>>)>,  // boards to explore with the cells of every grid changed since their last propagation
+    shared: Vec<Vec<(usize, usize)>>,  // fields of the grids that are the same field of the canvas
+    random: bool,  // branch on a random option instead of the first one
+    budget: Option<usize>,  // branches left before the search gives up, unlimited if None
+    exhausted: bool,  // a branch was refused because the budget ran out
+}
+
+const GRID: usize = 9;
+
+impl MultiLayout {
+    pub const ALL: [MultiLayout; 3] = [
+        MultiLayout::Samurai,
+        MultiLayout::Twin,
+        MultiLayout::Butterfly,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MultiLayout::Samurai   => "samurai",
+            MultiLayout::Twin      => "twin",
+            MultiLayout::Butterfly => "butterfly",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        MultiLayout::ALL.into_iter().find(|l| l.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn offsets(&self) -> &'static [(usize, usize)] {  // line and column of the upper left field of every grid on the canvas
+        match self {
+            MultiLayout::Samurai   => &[(0, 0), (0, 12), (6, 6), (12, 0), (12, 12)],
+            MultiLayout::Twin      => &[(0, 0), (6, 6)],
+            MultiLayout::Butterfly => &[(0, 0), (0, 3), (3, 0), (3, 3)],
+        }
+    }
+
+    pub fn size(&self) -> usize {  // lines and columns of the canvas
+        self.offsets().iter().map(|&(line, col)| line.max(col)).max().unwrap_or(0) + GRID
+    }
+}
+
+impl Iterator for MultiSolutions {
+    type Item = MultiBoard;
+
+    fn next(&mut self) -> Option<MultiBoard> {  // stack solver like Solutions, branching on the shortest option list of all grids
+        while let Some((mut current, changed)) = self.stack.pop() {
+            if !current.propagate(changed, &self.shared) {
+                continue;  // abandon this branch
+            }
+
+            let shortest = current.grids.iter().enumerate()
+                .flat_map(|(g, grid)| grid.content.iter().enumerate().filter_map(move |(idx, field)| match field {
+                    Field::OptionList(list) => Some((g, idx, *list)),
+                    Field::Number(_) => None,
+                }))
+                .min_by_key(|(_, _, list)| list.len());
+            let Some((g, idx, list)) = shortest else {
+                return Some(current);
+            };
+            if list.is_empty() {
+                continue;
+            }
+            match self.budget.as_mut() {
+                Some(0) => {
+                    self.stack.clear();
+                    self.exhausted = true;
+                    return None;
+                },
+                Some(budget) => *budget -= 1,
+                None => (),
+            }
+
+            let number = if self.random {
+                list.iter().nth(rand::thread_rng().gen_range(0..list.len())).unwrap()
+            } else {
+                list.first().unwrap()
+            };
+
+            let mut new_board = current.clone();
+            new_board.grids[g].remove_from_option_list(idx, number);
+            let queue = new_board.queue(g, vec!(idx));
+            self.stack.push((new_board, queue));
+
+            // a conflicting guess only abandons this branch, the alternative is still explored
+            if let Some(changed) = current.grids[g].assign(idx, number) {
+                let queue = current.queue(g, changed);
+                self.stack.push((current, queue));
+            }
+        }
+        None
+    }
+}
+
+impl MultiSolutions {
+    fn new(b: MultiBoard, random: bool, budget: Option<usize>) -> Self {
+        let queue = b.grids.iter().map(|grid| (0..grid.content.len()).collect()).collect();
+        let shared = b.shared();
+        MultiSolutions { stack: vec!((b, queue)), shared, random, budget, exhausted: false }
+    }
+
+    fn gave_up(&self) -> bool {  // true if the search was cut short, using up the budget on the last branch is not
+        self.exhausted
+    }
+}
+
+impl MultiBoard {
+    pub fn new(layout: MultiLayout) -> Self {
+        MultiBoard {
+            layout,
+            grids: layout.offsets().iter().map(|_| Board::empty(Shape::square(3))).collect(),
+        }
+    }
+
+    pub fn layout(&self) -> MultiLayout {
+        self.layout
+    }
+
+    pub fn grids(&self) -> &[Board] {  // the single grids in the order of the offsets of the layout, shared fields are held by every grid
+        &self.grids
+    }
+
+    pub fn read(&mut self, input: &str) -> Result<(), ParseError> {  // canvas text of the layout, empty lines and lines starting with '#' are skipped
+        *self = MultiBoard::new(self.layout);
+        let size = self.layout.size();
+
+        let mut rows: Vec<(usize, usize, &str)> = Vec::new();  // line number, character offset and text
+        let mut line_offset = 0;
+        for (line_no, line) in input.split('\n').enumerate() {
+            let offset = line_offset;
+            line_offset += line.chars().count() + 1;
+            if !(line.trim().is_empty() || line.trim_start().starts_with('#')) {
+                rows.push((line_no, offset, line.trim_end()));
+            }
+        }
+        if rows.len() != size {
+            return Err(ParseError::WrongRowCount { rows: rows.len(), expected: size });
+        }
+
+        for (line, &(line_no, offset, text)) in rows.iter().enumerate() {
+            let chars: Vec<char> = text.chars().collect();
+            if chars.len() > size {
+                return Err(ParseError::WrongRowLength { line: line_no, length: chars.len(), valid: vec!(size) });
+            }
+            for col in 0..size {
+                let fields = self.fields(line, col);
+                match (chars.get(col).copied(), fields.is_empty()) {
+                    (None | Some(' '), true) | (Some('.' | '0'), false) => (),
+                    (None, false) => return Err(ParseError::WrongRowLength { line: line_no, length: chars.len(), valid: vec!(size) }),
+                    (Some(c @ '1'..='9'), false) => {
+                        let val = c as usize - '1' as usize;
+                        for (g, idx) in fields {
+                            if !self.grids[g].set_num_index(idx, val) {
+                                return Err(self.conflict(g, idx, val));
+                            }
+                        }
+                    },
+                    (Some(c), _) => return Err(ParseError::InvalidToken { position: offset + col, token: c.to_string() }),
+                }
+            }
+        }
+
+        for grid in self.grids.iter_mut() {
+            grid.fix_givens();
+        }
+        Ok(())
+    }
+
+    pub fn print(&self) -> String {  // canvas text, see above
+        let size = self.layout.size();
+        let mut res = String::new();
+        for line in 0..size {
+            let row: String = (0..size).map(|col| match self.fields(line, col).first() {
+                Some(&(g, idx)) => self.grids[g].get_num_index(idx).map_or('.', |n| char::from(b'1' + n as u8)),
+                None => ' ',
+            }).collect();
+            res.push_str(row.trim_end());
+            res.push('\n');
+        }
+        res
+    }
+
+    pub fn solve(&self) -> Option<MultiBoard> {
+        self.solutions().next()
+    }
+
+    pub fn solutions(&self) -> MultiSolutions {  // lazy iterator over all solutions in depth-first order
+        MultiSolutions::new(self.clone(), false, None)
+    }
+
+    pub fn count_solutions(&self, limit: usize) -> usize {  // number of solutions, exploration stops once limit is reached
+        self.solutions().take(limit).count()
+    }
+
+    pub fn has_unique_solution(&self) -> bool {
+        self.count_solutions(2) == 1
+    }
+
+    pub fn generate(layout: MultiLayout) -> MultiBoard {  // unique puzzle, numbers of a random solution are deleted in random order as long as it stays unique
+        let solution = MultiSolutions::new(MultiBoard::new(layout), true, None).next().unwrap_or_else(|| MultiBoard::new(layout));
+        let size = layout.size();
+        let mut clues: Vec<Option<usize>> = (0..size * size).map(|i| {
+            let &(g, idx) = solution.fields(i / size, i % size).first()?;
+            solution.grids[g].get_num_index(idx)
+        }).collect();
+
+        let mut indices: Vec<usize> = (0..clues.len()).filter(|&i| clues[i].is_some()).collect();
+        indices.shuffle(&mut rand::thread_rng());
+
+        // a few uniqueness checks would search for minutes, their clues are kept once the budget runs out
+        for i in indices {
+            let clue = clues[i].take();
+            let mut solutions = MultiSolutions::new(MultiBoard::from_clues(layout, &clues), false, Some(BUDGET));
+            if solutions.by_ref().take(2).count() != 1 || solutions.gave_up() {
+                clues[i] = clue;
+            }
+        }
+
+        MultiBoard::from_clues(layout, &clues)
+    }
+
+    fn from_clues(layout: MultiLayout, clues: &[Option<usize>]) -> MultiBoard {  // clues of every field of the canvas
+        let mut b = MultiBoard::new(layout);
+        let size = layout.size();
+        for (i, clue) in clues.iter().enumerate() {
+            if let Some(num) = clue {
+                for (g, idx) in b.fields(i / size, i % size) {
+                    b.grids[g].set_num_index(idx, *num);
+                }
+            }
+        }
+        for grid in b.grids.iter_mut() {
+            grid.fix_givens();
+        }
+        b
+    }
+
+    fn fields(&self, line: usize, col: usize) -> Vec<(usize, usize)> {  // (grid, index) of the canvas field in every grid covering it
+        self.layout.offsets().iter().enumerate()
+            .filter(|&(_, &(l, c))| (l..l + GRID).contains(&line) && (c..c + GRID).contains(&col))
+            .map(|(g, &(l, c))| (g, (line - l) * GRID + col - c))
+            .collect()
+    }
+
+    fn shared(&self) -> Vec<Vec<(usize, usize)>> {  // canvas fields covered by more than one grid
+        let size = self.layout.size();
+        (0..size * size).map(|i| self.fields(i / size, i % size)).filter(|fields| fields.len() > 1).collect()
+    }
+
+    fn queue(&self, grid: usize, cells: Vec<usize>) -> Vec<Vec<usize>> {  // changed cells of a single grid
+        let mut queue = vec!(Vec::new(); self.grids.len());
+        queue[grid] = cells;
+        queue
+    }
+
+    fn conflict(&self, grid: usize, idx: usize, val: usize) -> ParseError {  // conflict within a grid with canvas positions
+        let (line, col) = self.layout.offsets()[grid];
+        match self.grids[grid].conflict(idx, val) {
+            ParseError::ConflictingGivens { cell, peer, value } =>
+                ParseError::ConflictingGivens { cell: (cell.0 + line, cell.1 + col), peer: (peer.0 + line, peer.1 + col), value },
+            e => e,
+        }
+    }
+
+    fn propagate(&mut self, mut queue: Vec<Vec<usize>>, shared: &[Vec<(usize, usize)>]) -> bool {  // propagate every grid and share the overlapping fields until nothing changes, false on contradiction
+        loop {
+            for (grid, cells) in self.grids.iter_mut().zip(queue) {
+                if !grid.propagate(cells) {
+                    return false;
+                }
+            }
+
+            // a number in one grid is set in the others, options are narrowed to those left in every grid
+            queue = vec!(Vec::new(); self.grids.len());
+            let mut changed = false;
+            for fields in shared.iter() {
+                let number = fields.iter().find_map(|&(g, idx)| self.grids[g].get_num_index(idx));
+                let options = fields.iter().fold(Candidates::full(GRID), |acc, &(g, idx)| match self.grids[g].content[idx] {
+                    Field::OptionList(list) => acc.intersection(list),
+                    Field::Number(_) => acc,
+                });
+                for &(g, idx) in fields.iter() {
+                    let grid = &mut self.grids[g];
+                    match (number, grid.content[idx]) {
+                        (Some(n), Field::Number(m)) if n != m => return false,
+                        (Some(n), Field::OptionList(list)) => {
+                            if !list.contains(n) {
+                                return false;
+                            }
+                            match grid.assign(idx, n) {
+                                Some(cells) => queue[g].extend(cells),
+                                None => return false,
+                            }
+                            changed = true;
+                        },
+                        (None, Field::OptionList(list)) if list != options => {
+                            grid.content[idx] = Field::OptionList(options);
+                            queue[g].push(idx);
+                            changed = true;
+                        },
+                        _ => (),
+                    }
+                }
+            }
+            if !changed {
+                return true;
+            }
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use crate::Board;
+use crate::Candidates;
+use crate::Encoding;
+use crate::Field;
+use crate::ParseError;
+use crate::Shape;
+use crate::grid;
+
+// Pencil marks: the candidates of every field, either as a wide grid with one row per line and the
+// candidates of a field written next to each other, or as a compact string of line_size symbols per
+// field where absent candidates are written as '.' or '0' (729 characters for 9x9).
+// Fields with a single candidate are read as numbers, so a board is read back with these
+// numbers removed from the candidates of their peers.
+
+fn encoding(line_size: usize) -> Encoding {
+    if line_size > 9 && Encoding::OneBased.supports(line_size) { Encoding::OneBased } else { Encoding::Numbers }
+}
+
+fn candidates(field: &Field) -> Candidates {
+    match field {
+        Field::Number(n) => Candidates::single(*n),
+        Field::OptionList(list) => *list,
+    }
+}
+
+fn is_separator_line(line: &str) -> bool {
+    let line = line.trim();
+    line.is_empty() || line.starts_with('#') || line.chars().all(|c| "-+=|.:'*".contains(c) || c.is_whitespace())
+}
+
+fn decode(b: &Board, position: usize, token: &str) -> Result<Candidates, ParseError> {  // candidates written next to each other
+    let encoding = encoding(b.line_size);
+    let width = encoding.width(b.line_size);
+    let chars: Vec<char> = token.chars().collect();
+    if !chars.len().is_multiple_of(width) {
+        return Err(ParseError::InvalidToken { position, token: token.to_string() });
+    }
+
+    let mut list = Candidates::empty();
+    for (i, symbol) in chars.chunks(width).enumerate() {
+        let symbol: String = symbol.iter().collect();
+        let value = encoding.decode(&symbol).ok_or(ParseError::InvalidToken { position: position + i * width, token: symbol })?;
+        if value == 0 || value > b.line_size {
+            return Err(ParseError::ValueOutOfRange { position: position + i * width, value, max: b.line_size });
+        }
+        list.insert(value - 1);
+    }
+    Ok(list)
+}
+
+fn fill(b: &mut Board, fields: Vec<Candidates>) -> Result<(), ParseError> {
+    // numbers last, they remove their value from the candidates of their peers
+    let mut numbers = Vec::new();
+    for (idx, list) in fields.into_iter().enumerate() {
+        if list.len() == 1 {
+            numbers.push((idx, list.first().unwrap_or(0)));
+        } else {
+            b.content[idx] = Field::OptionList(list);
+        }
+    }
+
+    for (idx, val) in numbers {
+        if !b.set_num_index(idx, val) {
+            return Err(b.conflict(idx, val));
+        }
+    }
+    b.fix_givens();
+    Ok(())
+}
+
+pub fn read_pencil_marks(b: &mut Board, input: &str) -> Result<(), ParseError> {  // wide grid or compact string
+    let compact = input.trim();
+    if !compact.is_empty() && !compact.contains(char::is_whitespace) && !compact.contains('|') {
+        read_compact(b, compact)
+    } else {
+        read_wide(b, input)
+    }
+}
+
+fn read_compact(b: &mut Board, input: &str) -> Result<(), ParseError> {
+    let chars: Vec<char> = input.chars().collect();
+    let shape = grid::LINE_SIZES.into_iter().find(|l| l.pow(3) * encoding(*l).width(*l) == chars.len()).and_then(Shape::for_line_size);
+    let Some(shape) = shape else {
+        b.content.clear();
+        b.origins.clear();
+        let valid = grid::LINE_SIZES.into_iter().map(|l| l.pow(3) * encoding(l).width(l)).collect();
+        return Err(ParseError::WrongLength { length: chars.len(), valid });
+    };
+    b.reset(shape);
+
+    let encoding = encoding(b.line_size);
+    let width = encoding.width(b.line_size);
+    let mut fields = Vec::with_capacity(b.content.len());
+    for (idx, field) in chars.chunks(width * b.line_size).enumerate() {
+        let mut list = Candidates::empty();
+        for (val, symbol) in field.chunks(width).enumerate() {
+            let symbol: String = symbol.iter().collect();
+            if symbol.chars().all(|c| c == '.' || c == '0') {
+                continue;
+            }
+            let position = (idx * b.line_size + val) * width;
+            if encoding.decode(&symbol) != Some(val + 1) {
+                return Err(ParseError::InvalidToken { position, token: symbol });
+            }
+            list.insert(val);
+        }
+        fields.push(list);
+    }
+    fill(b, fields)
+}
+
+fn read_wide(b: &mut Board, input: &str) -> Result<(), ParseError> {
+    let mut rows: Vec<Vec<(usize, String)>> = Vec::new();
+    let mut line_size = 0;
+    let mut line_offset = 0;
+    for (line_no, line) in input.split('\n').enumerate() {
+        let offset = line_offset;
+        line_offset += line.chars().count() + 1;
+        if is_separator_line(line) {
+            continue;
+        }
+
+        let row: Vec<(usize, String)> = grid::chunks(line).into_iter().map(|(o, c)| (o + offset, c)).collect();
+        if line_size == 0 {
+            if !grid::LINE_SIZES.contains(&row.len()) {
+                return Err(ParseError::WrongRowLength { line: line_no, length: row.len(), valid: grid::LINE_SIZES.to_vec() });
+            }
+            line_size = row.len();
+        } else if row.len() != line_size {
+            return Err(ParseError::WrongRowLength { line: line_no, length: row.len(), valid: vec!(line_size) });
+        }
+        rows.push(row);
+    }
+
+    if line_size == 0 || rows.len() != line_size {
+        return Err(ParseError::WrongRowCount { rows: rows.len(), expected: line_size });
+    }
+    b.reset(Shape::for_line_size(line_size).unwrap_or(Shape::square(0)));
+
+    let mut fields = Vec::with_capacity(b.content.len());
+    for (position, token) in rows.iter().flatten() {
+        fields.push(decode(b, *position, token)?);
+    }
+    fill(b, fields)
+}
+
+pub fn print_pencil_marks(b: &Board) -> String {
+    let encoding = encoding(b.line_size);
+    let tokens: Vec<String> = b.content.iter()
+        .map(|f| candidates(f).iter().map(|v| encoding.encode(v, b.line_size)).collect())
+        .collect();
+
+    // every column as wide as its longest list of candidates
+    let widths: Vec<usize> = (0..b.line_size)
+        .map(|col| (0..b.line_size).map(|line| tokens[b.idx_from_line_col(line, col)].chars().count()).max().unwrap_or(0).max(1))
+        .collect();
+
+    // jigsaw regions: '|' between fields of different regions, separator lines only above and below the grid
+    let regions = b.has_regions();
+    let mut separator = String::from("+");
+    if regions {
+        separator.push_str(&format!("{}+", "-".repeat(widths.iter().sum::<usize>() + 3 * b.line_size - 1)));
+    } else {
+        for quad in widths.chunks(b.shape.cols) {
+            let width: usize = quad.iter().sum::<usize>() + 2 * quad.len();
+            separator.push_str(&format!("{}+", "-".repeat(width)));
+        }
+    }
+
+    let mut res = String::new();
+    for line in 0..b.line_size {
+        if line == 0 || (!regions && line % b.shape.rows == 0) {
+            res.push_str(&format!("{}\n", separator));
+        }
+        res.push('|');
+        for col in 0..b.line_size {
+            res.push_str(&format!(" {0:1$} ", tokens[b.idx_from_line_col(line, col)], widths[col]));
+            if col == b.line_size - 1 {
+                res.push('|');
+            } else if regions {
+                res.push(if b.quad_of(line, col) != b.quad_of(line, col + 1) { '|' } else { ' ' });
+            } else if col % b.shape.cols == b.shape.cols - 1 {
+                res.push('|');
+            }
+        }
+        res.push('\n');
+    }
+    res.push_str(&format!("{}\n", separator));
+    res
+}
+
+pub fn print_candidates(b: &Board) -> String {  // compact form, one line
+    let encoding = encoding(b.line_size);
+    let blank = ".".repeat(encoding.width(b.line_size));
+    let mut res = String::new();
+    for field in b.content.iter() {
+        let list = candidates(field);
+        for val in 0..b.line_size {
+            if list.contains(val) {
+                res.push_str(&encoding.encode(val, b.line_size));
+            } else {
+                res.push_str(&blank);
+            }
+        }
+    }
+    res.push('\n');
+    res
+}
@@ -0,0 +1,210 @@
+use std::io::IsTerminal;
+
+use crate::Board;
+use crate::Encoding;
+use crate::Field;
+use crate::Origin;
+
+// Unicode box drawing renderer: heavy lines on box and region edges, light lines between fields,
+// optional candidate sub-grids inside empty fields and coordinate labels (rows A-I, columns 1-9).
+// With colors givens are bold, solver filled numbers cyan, entered numbers green, conflicts red and
+// the fields changed by the most recent step yellow.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RenderOptions {
+    pub candidates: bool,    // candidate sub-grid inside every empty field
+    pub labels: bool,        // row letters and column numbers, rows are numbered as well above 26 rows
+    pub blank: char,         // empty fields and missing candidates
+    pub encoding: Encoding,  // boards too large for the encoding are rendered as numbers
+    pub colors: Colors,
+    pub changed: Vec<usize>,  // field indices changed by the most recent step, see Step::changed
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Colors {
+    Never,
+    Always,
+    Auto,  // only if stdout is a terminal
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions { candidates: false, labels: false, blank: ' ', encoding: Encoding::Numbers, colors: Colors::Never, changed: Vec::new() }
+    }
+}
+
+const RESET: &str = "\x1b[0m";
+const GIVEN: &str = "\x1b[1m";
+const DERIVED: &str = "\x1b[36m";
+const ENTERED: &str = "\x1b[32m";
+const CONFLICT: &str = "\x1b[1;97;41m";
+const CHANGED: &str = "\x1b[1;30;43m";
+
+impl Colors {
+    pub const ALL: [Colors; 3] = [
+        Colors::Never,
+        Colors::Always,
+        Colors::Auto,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Colors::Never  => "never",
+            Colors::Always => "always",
+            Colors::Auto   => "auto",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Colors::ALL.into_iter().find(|c| c.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn enabled(&self) -> bool {
+        match self {
+            Colors::Never  => false,
+            Colors::Always => true,
+            Colors::Auto   => std::io::stdout().is_terminal(),
+        }
+    }
+}
+
+fn styles(b: &Board, options: &RenderOptions) -> Vec<Option<&'static str>> {  // ANSI style of every field, conflicts first
+    (0..b.content.len()).map(|idx| {
+        let conflict = match b.content[idx] {
+            Field::Number(n) => b.peers(idx).into_iter().any(|p| b.get_num_index(p) == Some(n)),
+            Field::OptionList(list) => list.is_empty(),
+        };
+        if conflict {
+            Some(CONFLICT)
+        } else if options.changed.contains(&idx) {
+            Some(CHANGED)
+        } else {
+            match b.origins[idx] {
+                Some(Origin::Given)   => Some(GIVEN),
+                Some(Origin::Derived) => Some(DERIVED),
+                Some(Origin::Entered) => Some(ENTERED),
+                None => None,
+            }
+        }
+    }).collect()
+}
+
+fn paint(text: String, style: Option<&str>) -> String {
+    match style {
+        Some(style) => format!("{}{}{}", style, text, RESET),
+        None => text,
+    }
+}
+
+pub fn print_colored(b: &Board, pretty_print: bool, options: &RenderOptions) -> String {
+    if !options.colors.enabled() {
+        return b.print_with(pretty_print, options.encoding);
+    }
+    let styles = styles(b, options);
+    b.print_styled(pretty_print, options.encoding, |idx, text| paint(text, styles[idx]))
+}
+
+fn row_label(b: &Board, line: usize) -> String {
+    if b.line_size <= 26 {
+        char::from(b'A' + line as u8).to_string()
+    } else {
+        (line + 1).to_string()
+    }
+}
+
+fn thick_right(b: &Board, line: usize, col: usize) -> bool {  // border between two boxes or regions
+    col == b.line_size - 1 || b.quad_of(line, col) != b.quad_of(line, col + 1)
+}
+
+fn thick_below(b: &Board, line: usize, col: usize) -> bool {
+    line == b.line_size - 1 || b.quad_of(line, col) != b.quad_of(line + 1, col)
+}
+
+fn rule(b: &Board, above: Option<usize>, inner: usize) -> String {  // horizontal line below line above, None for the top line
+    let last = b.line_size - 1;
+    let heavy = |col: usize| above.is_none_or(|line| thick_below(b, line, col));
+    let mut res = String::new();
+    for col in 0..b.line_size {
+        if col == 0 {
+            res.push(match above {
+                None => '┏',
+                Some(line) if line == last => '┗',
+                Some(_) => if heavy(0) { '┣' } else { '┠' },
+            });
+        }
+        res.push_str(&(if heavy(col) { '━' } else { '─' }).to_string().repeat(inner));
+        res.push(match above {
+            None if col == last => '┓',
+            None => if thick_right(b, 0, col) { '┳' } else { '┯' },
+            Some(line) if line == last && col == last => '┛',
+            Some(line) if line == last => if thick_right(b, line, col) { '┻' } else { '┷' },
+            Some(_) if col == last => if heavy(col) { '┫' } else { '┨' },
+            Some(line) => match (thick_right(b, line, col) || thick_right(b, line + 1, col), heavy(col) || heavy(col + 1)) {
+                (false, false) => '┼',
+                (true, false)  => '╂',
+                (false, true)  => '┿',
+                (true, true)   => '╋',
+            },
+        });
+    }
+    res
+}
+
+fn cell_line(b: &Board, options: &RenderOptions, encoding: Encoding, idx: usize, k: usize, inner: usize) -> String {  // text line k of a field
+    let width = encoding.width(b.line_size);
+    let blank = options.blank.to_string().repeat(width);
+    match b.content[idx] {
+        Field::Number(n) if !options.candidates || k == b.shape.rows / 2 =>
+            format!("{:^1$}", encoding.encode(n, b.line_size), inner),
+        Field::Number(_) => " ".repeat(inner),
+        Field::OptionList(_) if !options.candidates => format!("{:^1$}", blank, inner),
+        Field::OptionList(list) => {
+            let symbols: Vec<String> = (k * b.shape.cols..(k + 1) * b.shape.cols)
+                .map(|v| if list.contains(v) { encoding.encode(v, b.line_size) } else { blank.clone() })
+                .collect();
+            format!(" {} ", symbols.join(" "))
+        },
+    }
+}
+
+pub fn render(b: &Board, options: &RenderOptions) -> String {
+    if b.line_size == 0 {
+        return String::new();
+    }
+
+    let encoding = if options.encoding.supports(b.line_size) { options.encoding } else { Encoding::Numbers };
+    let width = encoding.width(b.line_size);
+    let (inner, text_lines) = if options.candidates { (b.shape.cols * (width + 1) + 1, b.shape.rows) } else { (width + 2, 1) };
+    let label_width = if options.labels { (0..b.line_size).map(|l| row_label(b, l).len()).max().unwrap_or(0) + 1 } else { 0 };
+    let margin = " ".repeat(label_width);
+    let styles = if options.colors.enabled() { styles(b, options) } else { vec!(None; b.content.len()) };
+
+    let mut res = String::new();
+    if options.labels {
+        res.push_str(&margin);
+        for col in 0..b.line_size {
+            res.push_str(&format!(" {:^1$}", col + 1, inner));
+        }
+        res = format!("{}\n", res.trim_end());
+    }
+
+    res.push_str(&format!("{}{}\n", margin, rule(b, None, inner)));
+    for line in 0..b.line_size {
+        for k in 0..text_lines {
+            if options.labels && k == text_lines / 2 {
+                res.push_str(&format!("{:<1$}", row_label(b, line), label_width));
+            } else {
+                res.push_str(&margin);
+            }
+
+            res.push('┃');
+            for col in 0..b.line_size {
+                let idx = b.idx_from_line_col(line, col);
+                res.push_str(&paint(cell_line(b, options, encoding, idx, k, inner), styles[idx]));
+                res.push(if thick_right(b, line, col) { '┃' } else { '│' });
+            }
+            res.push('\n');
+        }
+        res.push_str(&format!("{}{}\n", margin, rule(b, Some(line), inner)));
+    }
+    res
+}
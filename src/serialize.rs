@@ -0,0 +1,174 @@
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::Board;
+use crate::Cage;
+use crate::Candidates;
+use crate::Field;
+use crate::Origin;
+use crate::ParseError;
+use crate::Shape;
+
+// Stable schema of boards for serde, numbers count from one as in print:
+// {"base": 3, "givens": [5, 0, ...], "values": [0, 4, ...], "entered": [1, ...], "candidates": [[], [1, 2], ...]}
+// boards with rectangular boxes have "boxes": [2, 3] (lines and columns of a box) instead of the base.
+// jigsaw boards list the region of every field counting from zero in "regions", X-Sudoku boards have "diagonals": true.
+// killer boards list their cages with field indices and sums: "cages": [{"cells": [0, 1], "sum": 3}, ...].
+// givens and values hold one number per field, 0 marks fields without a given or filled in number.
+// entered lists the indices of values entered by the player, it is left out if there are none.
+// candidates lists the options of every empty field and may be left out to derive them from the numbers.
+// Fields serialize as their number or the list of their candidates.
+// Positions of errors are field indices.
+
+#[derive(Serialize, Deserialize)]
+pub struct BoardData {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    base: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    boxes: Option<(usize, usize)>,
+    givens: Vec<usize>,
+    values: Vec<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    entered: Vec<usize>,
+    #[serde(default)]
+    candidates: Option<Vec<Vec<usize>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    regions: Option<Vec<usize>>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    diagonals: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    cages: Vec<Cage>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum FieldData {
+    Number(usize),
+    OptionList(Vec<usize>),
+}
+
+fn values(list: &Candidates) -> Vec<usize> {
+    list.iter().map(|v| v + 1).collect()
+}
+
+fn candidates(position: usize, values: &[usize], max: usize) -> Result<Candidates, ParseError> {
+    let mut list = Candidates::empty();
+    for &value in values {
+        if value == 0 || value > max {
+            return Err(ParseError::ValueOutOfRange { position, value, max });
+        }
+        list.insert(value - 1);
+    }
+    Ok(list)
+}
+
+impl From<Field> for FieldData {
+    fn from(field: Field) -> Self {
+        match field {
+            Field::Number(n) => FieldData::Number(n + 1),
+            Field::OptionList(list) => FieldData::OptionList(values(&list)),
+        }
+    }
+}
+
+impl TryFrom<FieldData> for Field {
+    type Error = ParseError;
+
+    fn try_from(data: FieldData) -> Result<Self, Self::Error> {
+        match data {
+            FieldData::Number(n) if n == 0 || n > Candidates::CAPACITY =>
+                Err(ParseError::ValueOutOfRange { position: 0, value: n, max: Candidates::CAPACITY }),
+            FieldData::Number(n) => Ok(Field::Number(n - 1)),
+            FieldData::OptionList(list) => Ok(Field::OptionList(candidates(0, &list, Candidates::CAPACITY)?)),
+        }
+    }
+}
+
+impl From<Board> for BoardData {
+    fn from(b: Board) -> Self {
+        let number = |idx: usize, given: bool| match (b.content[idx], b.origins[idx]) {
+            (Field::Number(n), origin) if (origin == Some(Origin::Given)) == given => n + 1,
+            _ => 0,
+        };
+        BoardData {
+            base: if b.shape.rows == b.shape.cols { Some(b.shape.rows) } else { None },
+            boxes: if b.shape.rows == b.shape.cols { None } else { Some((b.shape.rows, b.shape.cols)) },
+            givens: (0..b.content.len()).map(|idx| number(idx, true)).collect(),
+            values: (0..b.content.len()).map(|idx| number(idx, false)).collect(),
+            entered: (0..b.content.len()).filter(|&idx| b.origins[idx] == Some(Origin::Entered)).collect(),
+            candidates: Some(b.content.iter().map(|f| match f {
+                Field::Number(_) => Vec::new(),
+                Field::OptionList(list) => values(list),
+            }).collect()),
+            regions: if b.has_regions() { Some(b.regions()) } else { None },
+            diagonals: b.diagonals,
+            cages: b.cages.to_vec(),
+        }
+    }
+}
+
+impl TryFrom<BoardData> for Board {
+    type Error = ParseError;
+
+    fn try_from(data: BoardData) -> Result<Self, Self::Error> {
+        let mut b = Board::new();
+        let shape = match (data.base, data.boxes) {
+            (Some(0) | None, None) if data.givens.is_empty() && data.values.is_empty() => return Ok(b),
+            (None, Some((rows, cols))) if Shape::new(rows, cols).is_supported() => Shape::new(rows, cols),
+            (None, Some((rows, _))) => return Err(ParseError::UnsupportedBase { base: rows }),
+            (Some(base), None) if (2..10).contains(&base) => Shape::square(base),
+            (base, _) => return Err(ParseError::UnsupportedBase { base: base.unwrap_or(0) }),
+        };
+        b.reset(shape);
+
+        let size = b.content.len();
+        for (list, length) in [("givens", data.givens.len()), ("values", data.values.len()), ("candidates", data.candidates.as_ref().map_or(size, |c| c.len()))] {
+            if length != size {
+                return Err(ParseError::WrongFieldCount { list, length, expected: size });
+            }
+        }
+
+        if let Some(regions) = &data.regions {
+            b.set_regions(regions)?;
+        }
+        b.diagonals = data.diagonals;
+        if !data.cages.is_empty() {
+            b.set_cages(data.cages)?;
+        }
+
+        // candidates first, numbers remove their value from the candidates of their peers
+        let mut numbers = Vec::new();
+        for idx in 0..size {
+            let value = match (data.givens[idx], data.values[idx]) {
+                (0, 0) => {
+                    if let Some(list) = &data.candidates {
+                        b.content[idx] = Field::OptionList(candidates(idx, &list[idx], b.line_size)?);
+                    }
+                    continue;
+                },
+                (given, 0) => given,
+                (0, value) => value,
+                _ => return Err(ParseError::GivenAndValue { position: idx }),
+            };
+            if value > b.line_size {
+                return Err(ParseError::ValueOutOfRange { position: idx, value, max: b.line_size });
+            }
+            numbers.push((idx, value - 1));
+        }
+
+        for (idx, val) in numbers {
+            if !b.set_num_index(idx, val) {
+                return Err(b.conflict(idx, val));
+            }
+            b.origins[idx] = Some(if data.givens[idx] != 0 { Origin::Given } else { Origin::Derived });
+        }
+
+        for &idx in data.entered.iter() {
+            match b.origins.get(idx) {
+                Some(Some(Origin::Derived)) => b.origins[idx] = Some(Origin::Entered),
+                _ => return Err(ParseError::NotDerived { position: idx }),
+            }
+        }
+        Ok(b)
+    }
+}
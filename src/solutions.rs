@@ -0,0 +1,95 @@
+use rand::Rng;
+
+use crate::Board;
+use crate::SolverStep;
+
+pub struct Solutions {
+    stack: Vec<(Board, Vec<usize>)>,  // boards to explore with the cells changed since their last propagation
+    random: bool,  // branch on a random option instead of the first one
+    budget: Option<usize>,  // branches left before the search gives up, unlimited if None
+    exhausted: bool,  // a branch was refused because the budget ran out
+    restart: usize,  // earlier searches given up, ties between fields and numbers are broken differently in every one
+}
+
+pub(crate) const BUDGET: usize = 250;  // branches of a uniqueness check while generating
+pub(crate) const RESTART_BUDGET: usize = 500;  // branches before solving starts over, growing by half with every restart
+
+impl Iterator for Solutions {
+    type Item = Board;
+
+    fn next(&mut self) -> Option<Board> {  // stack solver implementation
+        while let Some((mut current_board, changed)) = self.stack.pop() {
+            match current_board.next_solver_step(changed, self.restart) {
+                SolverStep::Solved => {
+                    return Some(current_board);
+                },
+                SolverStep::BranchOnOptionList(idx, first) => {
+                    match self.budget.as_mut() {
+                        Some(0) => {
+                            self.stack.clear();
+                            self.exhausted = true;
+                            return None;
+                        },
+                        Some(budget) => *budget -= 1,
+                        None => (),
+                    }
+                    if current_board.get_num_index(idx).is_some() {
+                        continue;
+                    }
+
+                    let number = match current_board.get_option_list(idx) {
+                        Some(list) if self.random => list.iter().nth(rand::thread_rng().gen_range(0..list.len())),
+                        _ => Some(first),
+                    };
+                    if number.is_none() {
+                        continue;
+                    }
+                    let number = number.unwrap();
+
+                    let mut new_board = current_board.clone();
+                    new_board.remove_from_option_list(idx, number);
+                    self.stack.push((new_board, vec!(idx)));
+
+                    // a conflicting guess only abandons this branch, the alternative is still explored
+                    if let Some(changed) = current_board.assign(idx, number) {
+                        self.stack.push((current_board, changed));
+                    }
+                },
+                SolverStep::Unsolvable => (), // abandon this branch
+            }
+        }
+        None
+    }
+}
+
+impl Solutions {
+    pub fn new(b: Board) -> Self {  // a board without fields has no solution
+        let cells = (0..b.content.len()).collect();
+        let stack = if b.content.is_empty() { Vec::new() } else { vec!((b, cells)) };
+        Solutions { stack, random: false, budget: None, exhausted: false, restart: 0 }
+    }
+
+    pub(crate) fn random(b: Board, budget: usize) -> Self {  // solutions in random order within a limited number of branches, used to fill templates
+        Solutions { random: true, budget: Some(budget), ..Solutions::new(b) }
+    }
+
+    pub(crate) fn limited(b: Board, budget: usize) -> Self {  // solutions in depth-first order within a limited number of branches
+        Solutions { budget: Some(budget), ..Solutions::new(b) }
+    }
+
+    pub(crate) fn restarted(b: Board, restart: usize, budget: usize) -> Self {  // solutions within a limited number of branches, breaking ties as in the given restart
+        Solutions { budget: Some(budget), restart, ..Solutions::new(b) }
+    }
+
+    pub(crate) fn gave_up(&self) -> bool {  // true if the search was cut short, using up the budget on the last branch is not
+        self.exhausted
+    }
+}
+
+pub(crate) fn scramble(val: usize, restart: usize) -> usize {  // order of fields and numbers in a restart, the natural order in the first search
+    if restart == 0 {
+        return val;
+    }
+    let x = (val as u64 ^ (restart as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    (x ^ (x >> 31)) as usize
+}
@@ -0,0 +1,79 @@
+use sudoku::Board;
+use sudoku::Colors;
+use sudoku::Encoding;
+use sudoku::RenderOptions;
+
+const GIVEN: &str = "\x1b[1m";
+const DERIVED: &str = "\x1b[36m";
+const ENTERED: &str = "\x1b[32m";
+const CONFLICT: &str = "\x1b[1;97;41m";
+const CHANGED: &str = "\x1b[1;30;43m";
+const RESET: &str = "\x1b[0m";
+
+fn small() -> Board {
+    let mut b = Board::new();
+    b.read("1...........3..4").unwrap();
+    b
+}
+
+fn colored(changed: Vec<usize>) -> RenderOptions {
+    RenderOptions { colors: Colors::Always, changed, ..RenderOptions::default() }
+}
+
+fn paint(style: &str, text: &str) -> String {
+    format!("{}{}{}", style, text, RESET)
+}
+
+#[test]
+fn fields_are_colored_by_origin() {
+    let mut b = small();
+    assert!(b.enter(0, 1, 1));
+    let text = b.print_colored(false, &colored(vec!(5)));
+    let expected = format!("{}{}...{}......{}..{}\n", paint(GIVEN, "1"), paint(ENTERED, "2"), paint(CHANGED, "."), paint(GIVEN, "3"), paint(GIVEN, "4"));
+    assert_eq!(text, expected);
+
+    let solved = small().solve().unwrap();
+    let text = solved.print_colored(false, &colored(Vec::new()));
+    assert!(text.starts_with(&paint(GIVEN, "1")));
+    assert_eq!(text.matches(DERIVED).count(), 13);
+    assert_eq!(text.matches(GIVEN).count(), 3);
+}
+
+#[test]
+fn fields_without_candidates_are_conflicts() {
+    let mut b = Board::new();
+    b.read_pencil_marks(".....2....3....4..3.12341....2...2..1......4..3....4..3..2..1...").unwrap();
+    let text = b.render(&colored(vec!(0)));
+    assert!(text.contains(&paint(CONFLICT, "   ")));
+    assert!(!text.contains(CHANGED));
+}
+
+#[test]
+fn colors_can_be_switched_off() {
+    let mut b = small();
+    assert!(b.enter(0, 1, 1));
+    let options = RenderOptions { colors: Colors::Never, changed: vec!(5), ..RenderOptions::default() };
+    assert_eq!(b.print_colored(true, &options), b.print(true));
+    assert_eq!(b.print_colored(false, &RenderOptions { encoding: Encoding::Letters, ..options.clone() }), b.print_with(false, Encoding::Letters));
+    assert!(!b.render(&options).contains('\x1b'));
+
+    for colors in Colors::ALL {
+        assert_eq!(Colors::from_name(colors.name()), Some(colors));
+    }
+    assert!(Colors::Always.enabled() && !Colors::Never.enabled());
+}
+
+#[test]
+fn trace_highlights_the_changed_fields() {
+    // every step of the logical solver is shown with the fields it changed
+    let mut b = Board::new();
+    b.read(".....1.69569.................1.65.2..37..25....2.....3....5.2.......7.1.9.64...8.").unwrap();
+    let logical = b.solve_logical();
+    assert!(logical.is_solved());
+    for step in logical.steps.iter() {
+        assert!(b.apply_step(step));
+        let text = b.print_colored(false, &colored(step.changed()));
+        assert_eq!(text.matches(CHANGED).count(), step.changed().len());
+    }
+    assert_eq!(b.print(false), logical.board.print(false));
+}
@@ -0,0 +1,73 @@
+use sudoku::Board;
+use sudoku::Candidates;
+use sudoku::Field;
+use sudoku::House;
+use sudoku::ParseError;
+use sudoku::Technique;
+
+// unique as X-Sudoku only, the first field is a hidden single of the main diagonal
+const X: &str = ".....1....84.........678..3..5..7.1.......7.....5...9........4......2....7...35..";
+
+fn x_board(input: &str) -> Board {
+    let mut b = Board::new();
+    b.read(input).unwrap();
+    b.set_diagonals(true).unwrap();
+    b
+}
+
+fn diagonal(b: &Board, d: usize) -> Vec<usize> {
+    let line_size = b.shape().line_size();
+    (0..line_size).map(|i| if d == 0 { i * line_size + i } else { i * line_size + line_size - 1 - i }).collect()
+}
+
+#[test]
+fn numbers_on_a_diagonal_conflict() {
+    let mut input = ".".repeat(81);
+    input.replace_range(0..1, "1");
+    input.replace_range(40..41, "1");
+    let mut b = Board::new();
+    b.read(&input).unwrap();
+    assert_eq!(b.set_diagonals(true), Err(ParseError::ConflictingGivens { cell: (4, 4), peer: (0, 0), value: 1 }));
+
+    // numbers leave the options of the other fields of their diagonals
+    let b = x_board(&format!("1{}", ".".repeat(80)));
+    assert!(b.has_diagonals());
+    assert!(matches!(b.iter().nth(80), Some(Field::OptionList(list)) if !list.contains(0)));
+    assert!(matches!(b.iter().nth(13), Some(Field::OptionList(list)) if list.contains(0)));
+}
+
+#[test]
+fn diagonals_are_houses_of_the_solvers() {
+    let b = x_board(X);
+    assert_eq!(b.count_solutions(2), 1);
+    let mut plain = Board::new();
+    plain.read(X).unwrap();
+    assert!(plain.count_solutions(2) > 1);
+
+    let logical = b.solve_logical();
+    let step = logical.steps.iter().find(|s| s.houses.contains(&House::Diagonal(0))).unwrap();
+    assert_eq!((step.technique, step.placement), (Technique::HiddenSingle, Some((0, 4))));
+
+    let mut empty = Board::new();
+    empty.read(&".".repeat(16)).unwrap();
+    assert_eq!(empty.count_solutions(1000), 288);
+    empty.set_diagonals(true).unwrap();
+    assert_eq!(empty.count_solutions(1000), 48);
+}
+
+#[test]
+fn generated_x_puzzles_are_unique() {
+    for base in [2, 3] {
+        let b = Board::generate_diagonal(base);
+        assert!(b.has_diagonals());
+        assert!(b.has_unique_solution());
+        let solution = b.solve().unwrap();
+        for d in 0..2 {
+            let values: Candidates = diagonal(&solution, d).into_iter().map(|idx| match solution.iter().nth(idx) {
+                Some(Field::Number(n)) => *n,
+                _ => panic!("unsolved field"),
+            }).collect();
+            assert_eq!(values, Candidates::full(base * base));
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use sudoku::Board;
+use sudoku::Cage;
+use sudoku::Format;
+use sudoku::Shape;
+use sudoku::Solver;
+
+const PUZZLES_3: &str = include_str!("../puzzles/puzzles-3.txt");
+const PUZZLES_4: &str = include_str!("../puzzles/puzzles-4.txt");
+const PUZZLES_5: &str = include_str!("../puzzles/puzzles-5.txt");
+const PUZZLES_6: &str = include_str!("../puzzles/puzzles-6.txt");
+
+const CLASSIC: &str = "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..";
+const SIX: &str = "3..5...5...2..46...1......2...5....4";
+const TWELVE: &str = "....07..02......0910....08..10....031112......0711........07....04......010704..09....0212....10..........0104......................................01..10......05..12......0510..020111......081208..........04......02..0611....08........02....040207....10......05........0805..........0104";
+const X: &str = "....384.......4.....81.9.2........4...........2.8.59.131...............3..7..1..2";
+
+const JIGSAW: &str = "\
+AAABBBBCC
+ABBBBBCCC
+AAAAACCCC
+DDEEEFFFF
+DDDEEEFFF
+DDDDEEEFF
+GGGHHHHHH
+GGGGHIIIH
+GGIIIIIIH
+
+1...7..2.
+...1...7.
+3........
+....26..9
+4..3.....
+.....96..
+5......8.
+61.8..4..
+.........
+";
+
+const KILLER: &str = "\
+ABCCDDDEF
+ABBBGDHEF
+AIIGGGHEE
+JIKKLHHMN
+JIOLLPMMN
+JQOLRPMST
+JQORRPUST
+VQWWXYUZT
+VVaaXYUZZ
+
+A=18 B=12 C=11 D=27 E=16 F=13 G=15 H=21 I=23
+J=21 K=9 L=23 M=26 N=3 O=12 P=12 Q=17 R=21
+S=10 T=16 U=13 V=14 W=5 X=12 Y=8 Z=17 a=10
+";
+
+fn read(input: &str) -> Board {
+    let mut b = Board::new();
+    b.read(input).unwrap();
+    b
+}
+
+fn read_format(input: &str, format: Format) -> Board {
+    let mut b = Board::new();
+    b.read_format(input, format).unwrap();
+    b
+}
+
+fn assert_agree(b: &Board, limit: usize) {  // same number of solutions, the one found by dlx is a solution of the stack solver
+    let stack: Vec<String> = b.solutions().take(limit).map(|s| s.print(false)).collect();
+    assert_eq!(b.count_solutions_with(Solver::DancingLinks, limit), stack.len());
+    assert_eq!(b.count_solutions_with(Solver::Stack, limit), stack.len());
+    match b.clone().solve_with(Solver::DancingLinks) {
+        Some(s) if stack.len() < limit => assert!(stack.contains(&s.print(false))),
+        Some(s) => {
+            // not all solutions were listed, the one of dlx has to complete the givens
+            let (puzzle, solution) = (b.print(false), s.print(false));
+            assert!(!solution.contains('.'));
+            assert!(puzzle.chars().zip(solution.chars()).all(|(p, s)| p == '.' || p == s));
+        },
+        None => assert!(stack.is_empty()),
+    }
+}
+
+#[test]
+fn classic_boards_agree() {
+    let b = read(CLASSIC);
+    assert_agree(&b, 2);
+    assert_eq!(b.count_solutions_with(Solver::DancingLinks, 2), 1);
+    assert_eq!(b.clone().solve_with(Solver::DancingLinks).map(|s| s.print(false)), b.solve().map(|s| s.print(false)));
+}
+
+#[test]
+fn rectangular_boxes_agree() {
+    for (input, shape) in [(SIX, Shape::new(2, 3)), (TWELVE, Shape::new(3, 4))] {
+        let mut b = Board::new();
+        b.read_with_shape(input, shape).unwrap();
+        assert_eq!(b.shape(), shape);
+        assert_agree(&b, 2);
+        assert_eq!(b.count_solutions_with(Solver::DancingLinks, 2), 1);
+    }
+}
+
+#[test]
+fn jigsaw_x_and_killer_boards_agree() {
+    let mut x = read(X);
+    x.set_diagonals(true).unwrap();
+    for b in [read_format(JIGSAW, Format::Jigsaw), x, read_format(KILLER, Format::Killer)] {
+        assert_agree(&b, 2);
+        assert_eq!(b.count_solutions_with(Solver::DancingLinks, 2), 1);
+    }
+}
+
+#[test]
+fn large_boards_agree() {
+    // the puzzles have many solutions, so both solutions only have to complete the givens
+    for line in [PUZZLES_4, PUZZLES_5, PUZZLES_6].into_iter().flat_map(str::lines) {
+        let b = read(line);
+        let puzzle = b.print(false);
+        for solver in [Solver::DancingLinks, Solver::Stack] {
+            let solution = b.clone().solve_with(solver).unwrap().print(false);
+            let solution = solution.trim();
+            assert!(!solution.contains('.'));
+            assert!(puzzle.chars().zip(solution.chars()).all(|(p, s)| p == '.' || p == s));
+            // a complete grid is its own and only solution
+            assert_eq!(read(solution).count_solutions_with(Solver::Stack, 2), 1);
+        }
+    }
+}
+
+#[test]
+fn unsolvable_board_has_no_solutions() {
+    // the first line needs 1 and 9 in its corners, but the columns of both already hold a 1
+    let b = read(&format!(".2345678.1{}1{}", ".".repeat(16), ".".repeat(54)));
+    assert_agree(&b, 2);
+    assert_eq!(b.count_solutions_with(Solver::DancingLinks, 2), 0);
+    assert!(b.solve_with(Solver::DancingLinks).is_none());
+}
+
+#[test]
+fn multiple_solutions_are_counted() {
+    let b = read(&".".repeat(16));
+    assert_agree(&b, 1000);
+    assert_eq!(b.count_solutions_with(Solver::DancingLinks, 1000), 288);
+    assert_eq!(b.count_solutions_with(Solver::DancingLinks, 10), 10);
+
+    // the puzzles of the file have many solutions
+    for line in PUZZLES_3.lines().take(3) {
+        let b = read(line);
+        assert_agree(&b, 20);
+        assert_eq!(b.count_solutions_with(Solver::DancingLinks, 20), 20);
+    }
+
+    // a killer cage alone leaves the order of its numbers open
+    let mut b = read(&".".repeat(16));
+    b.set_cages(vec!(Cage::new(vec!(0, 1), 3))).unwrap();
+    assert_agree(&b, 1000);
+    assert_eq!(b.count_solutions_with(Solver::DancingLinks, 1000), 288 / 6);
+}
@@ -0,0 +1,49 @@
+use sudoku::Board;
+use sudoku::Encoding;
+use sudoku::ParseError;
+
+const PUZZLES_4: &str = include_str!("../puzzles/puzzles-4.txt");
+const PUZZLES_5: &str = include_str!("../puzzles/puzzles-5.txt");
+
+#[test]
+fn letters_on_small_boards_are_invalid_tokens() {
+    let mut b = Board::new();
+    let input = format!("1A3456789{}", ".".repeat(72));
+    assert_eq!(b.read(&input), Err(ParseError::InvalidToken { position: 1, token: "A".to_string() }));
+
+    let mut b = Board::new();
+    assert_eq!(b.read("..x............."), Err(ParseError::InvalidToken { position: 2, token: "x".to_string() }));
+}
+
+#[test]
+fn single_character_encodings_round_trip() {
+    for input in [PUZZLES_4.lines().next().unwrap(), PUZZLES_5.lines().next().unwrap()] {
+        let mut b = Board::new();
+        b.read(input).unwrap();
+        for encoding in Encoding::ALL.into_iter().filter(|e| e.supports(b.shape().line_size())) {
+            let text = b.print_with(false, encoding);
+            let mut c = Board::new();
+            c.read(text.trim()).unwrap();
+            assert_eq!(c.print(false), b.print(false), "{}", encoding.name());
+        }
+    }
+}
+
+#[test]
+fn explicit_encoding_decodes_lower_case() {
+    let mut b = Board::new();
+    b.read_with(&format!("a{}", ".".repeat(255)), Encoding::ZeroBased).unwrap();
+    assert_eq!(b.print_with(false, Encoding::ZeroBased).chars().next(), Some('A'));
+}
+
+#[test]
+fn ambiguous_symbols_need_an_explicit_encoding() {
+    let input = format!("1{}", ".".repeat(255));
+    let mut b = Board::new();
+    assert_eq!(b.read(&input), Err(ParseError::AmbiguousEncoding { line_size: 16 }));
+
+    b.read_with(&input, Encoding::OneBased).unwrap();
+    assert!(b.print(false).starts_with("01"));
+    b.read_with(&input, Encoding::ZeroBased).unwrap();
+    assert!(b.print(false).starts_with("02"));
+}
@@ -0,0 +1,204 @@
+use sudoku::Board;
+use sudoku::Difficulty;
+use sudoku::Format;
+use sudoku::Metadata;
+use sudoku::ParseError;
+use sudoku::Shape;
+
+const ESCARGOT: &str = "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..";
+const PUZZLES_4: &str = include_str!("../puzzles/puzzles-4.txt");
+
+const SDK: &str = "\
+#AArto Inkala
+#Cone
+#Ctwo
+#LHard
+1....7.9.
+.3..2...8
+..96..5..
+..53..9..
+.1..8...2
+6....4...
+3......1.
+.4......7
+..7...3..
+";
+
+const SS: &str = "\
+*-----------*
+|1..|..7|.9.|
+|.3.|.2.|..8|
+|..9|6..|5..|
+|---+---+---|
+|..5|3..|9..|
+|.1.|.8.|..2|
+|6..|..4|...|
+|---+---+---|
+|3..|...|.1.|
+|.4.|...|..7|
+|..7|...|3..|
+*-----------*
+";
+
+const JIGSAW: &str = "\
+#AA. Jigsaw
+AAABBBCCC
+AAABBBCCC
+AAABBBCDC
+EEEFFFFDC
+EEEFFFFDD
+EEEFDDDDD
+GGGHHHHII
+GGGHHIIII
+GGGHHHIII
+
+......2.4
+....6..89
+..8.7....
+...31..7.
+35...49..
+7........
+.........
+927..5...
+..1.....3
+";
+
+fn read(input: &str, format: Format) -> Result<(Board, Metadata), ParseError> {
+    let mut b = Board::new();
+    b.read_format(input, format).map(|metadata| (b, metadata))
+}
+
+fn escargot() -> Board {
+    let mut b = Board::new();
+    b.read(ESCARGOT).unwrap();
+    b
+}
+
+fn layout(b: &Board) -> Vec<usize> {  // regions numbered in the order of their first field
+    let mut order = Vec::new();
+    b.regions().into_iter().map(|r| match order.iter().position(|&o| o == r) {
+        Some(name) => name,
+        None => {
+            order.push(r);
+            order.len() - 1
+        },
+    }).collect()
+}
+
+fn assert_round_trip(b: &Board, format: Format, metadata: &Metadata) {
+    let text = b.print_format(format, metadata);
+    let (c, read_back) = read(&text, format).unwrap();
+    assert_eq!(c.print(false), b.print(false), "{}", format.name());
+    assert_eq!(layout(&c), layout(b), "{}", format.name());
+    assert_eq!(c.print_format(format, &read_back), text, "{}", format.name());
+}
+
+#[test]
+fn sdk_keeps_metadata() {
+    let (b, metadata) = read(SDK, Format::Sdk).unwrap();
+    assert_eq!(b.print(false), escargot().print(false));
+    assert_eq!(metadata.author(), Some("Arto Inkala"));
+    assert_eq!(metadata.difficulty(), Some(Difficulty::Hard));
+    assert_eq!(metadata.entries().iter().filter(|(code, _)| *code == Metadata::COMMENT).count(), 2);
+    assert_eq!(b.print_format(Format::Sdk, &metadata), SDK);
+
+    let mut metadata = Metadata::new();
+    metadata.push(Metadata::SOURCE, "the newspaper");
+    metadata.set(Metadata::LEVEL, "Easy");
+    metadata.set(Metadata::LEVEL, "Expert");
+    assert_eq!(metadata.entries().len(), 2);
+    assert_round_trip(&b, Format::Sdk, &metadata);
+
+    // solver state in later sections is skipped
+    let sections = format!("[Puzzle]\n{}[State]\n{}", SDK, ".".repeat(81));
+    let (c, metadata) = read(&sections, Format::Sdk).unwrap();
+    assert_eq!(c.print(false), b.print(false));
+    assert_eq!(metadata.author(), Some("Arto Inkala"));
+}
+
+#[test]
+fn formats_round_trip() {
+    let mut metadata = Metadata::new();
+    metadata.push(Metadata::AUTHOR, "Arto Inkala");
+    for format in [Format::Sdk, Format::Sdm, Format::Ss] {
+        assert_round_trip(&escargot(), format, &metadata);
+        assert_round_trip(&Board::generate_unique(Shape::new(2, 3)), format, &metadata);
+        assert_round_trip(&Board::generate(4), format, &metadata);
+    }
+
+    let (b, _) = read(SS, Format::Ss).unwrap();
+    assert_eq!(b.print(false), escargot().print(false));
+    assert_eq!(b.print_format(Format::Ss, &Metadata::new()), SS);
+
+    // numbers entered by the player are written as well
+    let mut b = escargot();
+    assert!(b.enter(0, 1, 7));
+    assert_eq!(read(&b.print_format(Format::Sdk, &metadata), Format::Sdk).unwrap().0.print(false), b.print(false));
+}
+
+#[test]
+fn largest_boards_round_trip() {
+    // above 35x35 fields take two digits, empty ones can't be written as '0'
+    let mut b = Board::new();
+    b.read(&"..".repeat(81 * 81)).unwrap();
+    assert!(b.enter(0, 1, 80) && b.enter(80, 79, 0));
+    for format in [Format::Sdk, Format::Sdm, Format::Ss] {
+        assert_round_trip(&b, format, &Metadata::new());
+    }
+}
+
+#[test]
+fn sdm_holds_one_puzzle_per_line() {
+    let mut b = Board::new();
+    b.read(PUZZLES_4.lines().next().unwrap()).unwrap();
+    let text = b.print_format(Format::Sdm, &Metadata::new());
+    let line = text.trim_end();
+    assert_eq!(line.len(), 256);
+    assert!(line.contains('0') && !line.contains('.'));
+
+    let file = format!("# comment\n{}\n\n{}\n", escargot().print_format(Format::Sdm, &Metadata::new()), line);
+    let puzzles = Format::Sdm.puzzles(&file);
+    assert_eq!(puzzles.len(), 2);
+    assert_eq!(read(puzzles[0], Format::Sdm).unwrap().0.print(false), escargot().print(false));
+    assert_eq!(read(puzzles[1], Format::Sdm).unwrap().0.print(false), b.print(false));
+    assert_eq!(Format::Sdk.puzzles(&file), vec!(file.as_str()));
+}
+
+#[test]
+fn jigsaw_keeps_layout_and_metadata() {
+    let (b, metadata) = read(JIGSAW, Format::Jigsaw).unwrap();
+    assert!(b.has_regions());
+    assert_eq!(b.regions()[25], 3);
+    assert_eq!(metadata.author(), Some("A. Jigsaw"));
+    assert_eq!(b.count_solutions(2), 1);
+    assert_eq!(b.print_format(Format::Jigsaw, &metadata), JIGSAW);
+    assert_round_trip(&b, Format::Jigsaw, &Metadata::new());
+    assert_round_trip(&Board::generate_random_jigsaw(Shape::new(2, 3)), Format::Jigsaw, &metadata);
+}
+
+#[test]
+fn format_names() {
+    for format in Format::ALL {
+        assert_eq!(Format::from_name(format.name()), Some(format));
+    }
+    assert_eq!(Format::from_name("puzzle.SDK"), Some(Format::Sdk));
+    assert_eq!(Format::from_name(".ss"), Some(Format::Ss));
+    assert_eq!(Format::from_name("txt"), None);
+}
+
+#[test]
+fn parse_errors() {
+    let short: String = SDK.lines().take(12).map(|l| format!("{}\n", l)).collect();
+    assert_eq!(read(&short, Format::Sdk).err(), Some(ParseError::WrongRowCount { rows: 8, expected: 9 }));
+    assert_eq!(read(&SDK.replace(".3..2...8", ".3..2..?8"), Format::Sdk).err(), Some(ParseError::InvalidToken { position: 21, token: "?".to_string() }));
+    assert!(matches!(read(&SDK.replace(".3..2...8", ".3..2...88"), Format::Sdk), Err(ParseError::WrongRowLength { line: 5, length: 10, .. })));
+    assert_eq!(read(&SDK.replace("..53..9..", "..53..9.5"), Format::Sdk).err(), Some(ParseError::ConflictingGivens { cell: (3, 8), peer: (3, 2), value: 5 }));
+    assert!(matches!(read(&SS.replace("|.1.|.8.|..2|", "|.1.|.8.|..2.|"), Format::Ss), Err(ParseError::WrongRowLength { .. })));
+    assert!(matches!(read(&format!("{}1", ESCARGOT.replace('.', "0")), Format::Sdm), Err(ParseError::WrongLength { length: 82, .. })));
+
+    assert_eq!(read(&JIGSAW.replace("GGGHHHIII\n", "GGGHHIIII\n"), Format::Jigsaw).err(), Some(ParseError::WrongRegionSize { region: 7, size: 8, expected: 9 }));
+    assert_eq!(read(&JIGSAW.replace("EEEFFFFDC", "EEEFFFFDCC"), Format::Jigsaw).err(), Some(ParseError::WrongRowLength { line: 4, length: 10, valid: vec!(9) }));
+    let layout: String = JIGSAW.lines().take(6).map(|l| format!("{}\n", l)).collect();
+    assert_eq!(read(&layout, Format::Jigsaw).err(), Some(ParseError::WrongRowCount { rows: 5, expected: 9 }));
+}
+
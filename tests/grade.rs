@@ -0,0 +1,77 @@
+use sudoku::Board;
+use sudoku::Difficulty;
+use sudoku::Shape;
+use sudoku::Technique;
+
+const ESCARGOT: &str = "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..";
+
+fn board(input: &str) -> Board {
+    let mut b = Board::new();
+    b.read(input).unwrap();
+    b
+}
+
+fn assert_grade(input: &str, difficulty: Difficulty, hardest: Technique, count: usize, score: f32) {
+    let grade = board(input).grade();
+    assert_eq!((grade.difficulty, grade.hardest, grade.hardest_count), (difficulty, Some(hardest), count), "{}", input);
+    assert!((grade.score - score).abs() < 1e-4, "{} scored {}", input, grade.score);
+}
+
+#[test]
+fn puzzles_are_graded_by_their_hardest_technique() {
+    assert_grade("2.........3..14.82...7..3..68........9..43..1..1.6......9....6.36....41.47..9.5..", Difficulty::Easy, Technique::HiddenSingle, 9, 1.58);
+    assert_grade(".....1.69569.................1.65.2..37..25....2.....3....5.2.......7.1.9.64...8.", Difficulty::Medium, Technique::PointingPair, 2, 2.61);
+    assert_grade("..8....7..5...4......2.34..3..41.8.292.8...5...4..7....4.7....6..516.............", Difficulty::Hard, Technique::XYWing, 1, 4.2);
+    assert_grade("......53.3..42....6.1..3.2.9.4.......25...9...3...6......938..5..9.5...2....1...4", Difficulty::Expert, Technique::SimpleColoring, 2, 5.61);
+
+    // a technique needed more than three times raises the difficulty by one level
+    assert_grade("1....6352....8...12..3...7.....62..498...4..5.......8....97....846........9......", Difficulty::Hard, Technique::PointingPair, 4, 2.63);
+}
+
+#[test]
+fn guessing_is_diabolical() {
+    let grade = board(ESCARGOT).grade();
+    assert_eq!(grade.difficulty, Difficulty::Diabolical);
+    assert_eq!(grade.score, 9.0);
+
+    // a solved board needs no technique at all
+    let grade = board(ESCARGOT).solve().unwrap().grade();
+    assert_eq!((grade.difficulty, grade.hardest, grade.hardest_count, grade.score), (Difficulty::Easy, None, 0, 0.0));
+}
+
+#[test]
+fn difficulty_names() {
+    for difficulty in Difficulty::ALL {
+        assert_eq!(Difficulty::from_name(difficulty.name()), Some(difficulty));
+        assert_eq!(Difficulty::from_name(&difficulty.name().to_uppercase()), Some(difficulty));
+    }
+    assert!(Difficulty::ALL.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(Difficulty::from_name("impossible"), None);
+}
+
+#[test]
+fn generated_puzzles_lie_within_their_band() {
+    for band in [Difficulty::Easy..=Difficulty::Easy, Difficulty::Medium..=Difficulty::Hard, Difficulty::Diabolical..=Difficulty::Diabolical] {
+        let b = Board::generate_with_difficulty(3, band.clone());
+        assert!(band.contains(&b.grade().difficulty), "{:?} graded {:?}", band, b.grade());
+        assert!(b.has_unique_solution());
+    }
+
+    let b = Board::generate_with_difficulty(Shape::new(2, 3), Difficulty::Easy..=Difficulty::Medium);
+    assert!(b.grade().difficulty <= Difficulty::Medium);
+    assert!(b.has_unique_solution());
+}
+
+#[test]
+fn variants_lie_within_their_band() {
+    let band = Difficulty::Easy..=Difficulty::Medium;
+    for b in [
+        Board::generate_diagonal_with_difficulty(3, band.clone()),
+        Board::generate_random_jigsaw_with_difficulty(3, band.clone()),
+        Board::generate_jigsaw_with_difficulty(&Board::random_regions(3), band.clone()).unwrap(),
+        Board::generate_killer_with_difficulty(2, band.clone()),
+    ] {
+        assert!(band.contains(&b.grade().difficulty), "graded {:?}", b.grade());
+        assert!(b.has_unique_solution());
+    }
+}
@@ -0,0 +1,88 @@
+use sudoku::Board;
+use sudoku::GridReader;
+use sudoku::ParseError;
+use sudoku::Shape;
+
+const ESCARGOT: &str = "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..";
+
+// escargot typed by hand: separated fields, '0' and '_' for empty fields, comments and separator lines
+const TYPED: &str = "\
+# Escargot
+1 0 0 | 0 0 7 | 0 9 0
+0 3 0 | 0 2 0 | 0 0 8
+0 0 9 | 6 0 0 | 5 0 0
+------+-------+------
+0 0 5 | 3 0 0 | 9 0 0
+// the middle band
+_ 1 _ | _ 8 _ | _ _ 2
+6 0 0 | 0 0 4 | 0 0 0
+======+=======+======
+3 0 0 | 0 0 0 | 0 1 0
+0 4 0 | 0 0 0 | 0 0 7
+0 0 7 | 0 0 0 | 3 0 0
+";
+
+fn escargot() -> Board {
+    let mut b = Board::new();
+    b.read(ESCARGOT).unwrap();
+    b
+}
+
+#[test]
+fn typed_grids_are_read() {
+    let mut b = Board::new();
+    b.read_grid(TYPED).unwrap();
+    assert_eq!(b.print(false), escargot().print(false));
+
+    // two digit numbers, separated or packed
+    let mut c = Board::new();
+    c.read_grid(&Board::generate(4).print(true)).unwrap();
+    let separated: String = c.print(false).trim().chars().collect::<Vec<char>>()
+        .chunks(32).map(|row| row.chunks(2).map(|f| f.iter().collect::<String>()).collect::<Vec<String>>().join(" ") + "\n").collect();
+    let mut d = Board::new();
+    d.read_grid(&separated).unwrap();
+    assert_eq!(d.print(false), c.print(false));
+}
+
+#[test]
+fn grids_round_trip() {
+    for b in [escargot(), Board::generate_unique(Shape::new(2, 3)), Board::generate(4), Board::generate_unique(Shape::new(3, 4))] {
+        let mut c = Board::new();
+        c.read_grid(&b.print(true)).unwrap();
+        assert_eq!(c.print(false), b.print(false));
+        assert_eq!(c.shape(), b.shape());
+    }
+
+    let grid = escargot().print(true);
+    let mut b = Board::new();
+    let short: String = grid.lines().take(5).map(|l| format!("{}\n", l)).collect();
+    assert!(matches!(b.read_grid(&short), Err(ParseError::WrongRowCount { expected: 9, .. })));
+    let long = grid.replacen(".3.|.2.|..8", ".3.|.2.|..85", 1);
+    assert_eq!(b.read_grid(&long), Err(ParseError::WrongRowLength { line: 1, length: 10, valid: vec!(9) }));
+}
+
+#[test]
+fn grid_reader_collects_streamed_rows() {
+    let mut reader = GridReader::new();
+    let mut grids = Vec::new();
+    for line in format!("{}\n{}", TYPED, escargot().print(true)).lines() {
+        if let Some(grid) = reader.push_line(line).unwrap() {
+            grids.push(grid);
+        }
+    }
+    assert!(reader.is_empty());
+    assert_eq!(reader.finish(), Ok(()));
+    assert_eq!(grids.len(), 2);
+    for grid in grids {
+        let mut b = Board::new();
+        b.read_grid(&grid).unwrap();
+        assert_eq!(b.print(false), escargot().print(false));
+    }
+
+    // an incomplete grid is reported once the input ends
+    for line in TYPED.lines().take(4) {
+        assert_eq!(reader.push_line(line), Ok(None));
+    }
+    assert_eq!(reader.finish(), Err(ParseError::WrongRowCount { rows: 3, expected: 9 }));
+    assert!(matches!(reader.push_line("1 2 3"), Err(ParseError::WrongRowLength { length: 3, .. })));
+}
@@ -0,0 +1,55 @@
+use sudoku::Board;
+use sudoku::Field;
+use sudoku::House;
+use sudoku::Technique;
+
+const ESCARGOT: &str = "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..";
+
+fn board(input: &str) -> Board {
+    let mut b = Board::new();
+    b.read(input).unwrap();
+    b
+}
+
+#[test]
+fn hints_name_the_cell_the_value_and_the_houses() {
+    let b = board(&format!("12345678.{}", ".".repeat(72)));
+    let hint = b.hint().unwrap();
+    assert_eq!(hint.technique, Technique::NakedSingle);
+    assert_eq!(hint.placement, Some((8, 8)));
+    assert_eq!(hint.houses, vec!(House::Line(0), House::Col(8), House::Quad(2)));
+    assert_eq!(hint.changed(), vec!(8));
+    assert_eq!(hint.describe(&b), "Naked Single: r1c9=9");
+
+    // asking for a hint leaves the board alone, applying it places the number
+    assert!(matches!(b.iter().nth(8), Some(Field::OptionList(_))));
+    let mut c = b.clone();
+    assert!(c.apply_step(&hint));
+    assert!(matches!(c.iter().nth(8), Some(Field::Number(8))));
+    assert_ne!(c.hint(), Some(hint));
+}
+
+#[test]
+fn eliminations_are_described_as_removed_candidates() {
+    let mut b = board(".....1.69569.................1.65.2..37..25....2.....3....5.2.......7.1.9.64...8.");
+    while let Some(hint) = b.hint() {
+        if hint.technique == Technique::PointingPair {
+            assert!(hint.placement.is_none() && !hint.eliminations.is_empty());
+            assert!(hint.describe(&b).starts_with("Pointing Pair: r"));
+            assert_eq!(hint.describe(&b).matches('-').count(), hint.eliminations.len());
+            return;
+        }
+        assert!(b.apply_step(&hint));
+    }
+    panic!("no pointing pair");
+}
+
+#[test]
+fn no_hint_when_solved_or_only_guessing_is_left() {
+    let stuck = board(ESCARGOT).solve_logical();
+    assert!(!stuck.is_solved());
+    assert_eq!(stuck.board.hint(), None);
+
+    let solved = board(ESCARGOT).solve().unwrap();
+    assert_eq!(solved.hint(), None);
+}
@@ -0,0 +1,56 @@
+use sudoku::Board;
+use sudoku::Candidates;
+use sudoku::Field;
+use sudoku::Format;
+use sudoku::Metadata;
+
+fn assert_valid_regions(b: &Board) {  // every region has a field per number and holds each number of the solution once
+    let solution = b.clone().solve().unwrap();
+    let numbers: Vec<usize> = solution.iter().map(|f| match f {
+        Field::Number(n) => *n,
+        Field::OptionList(_) => panic!("unsolved field"),
+    }).collect();
+    let regions = b.regions();
+    let line_size = b.shape().line_size();
+    for region in 0..line_size {
+        let values: Candidates = (0..regions.len()).filter(|&idx| regions[idx] == region).map(|idx| numbers[idx]).collect();
+        assert_eq!(regions.iter().filter(|&&r| r == region).count(), line_size);
+        assert_eq!(values, Candidates::full(line_size));
+    }
+}
+
+#[test]
+fn random_jigsaws_are_unique() {
+    for _ in 0..10 {
+        let b = Board::generate_random_jigsaw(3);
+        assert!(b.has_regions());
+        assert!(b.has_unique_solution());
+        assert_valid_regions(&b);
+    }
+}
+
+#[test]
+fn jigsaws_keep_their_layout() {
+    let regions = Board::random_regions(3);
+    let b = Board::generate_jigsaw(&regions).unwrap();
+    assert_eq!(b.regions(), regions);
+    assert!(b.has_unique_solution());
+
+    assert!(Board::generate_jigsaw(&[0; 81]).is_none());
+}
+
+#[test]
+fn jigsaws_round_trip() {
+    let b = Board::generate_random_jigsaw(3);
+    let text = b.print_format(Format::Jigsaw, &Metadata::new());
+    let mut c = Board::new();
+    c.read_format(&text, Format::Jigsaw).unwrap();
+    assert_eq!(c.print_format(Format::Jigsaw, &Metadata::new()), text);
+    assert_eq!(c.print(false), b.print(false));
+
+    // pretty printed grids only show the borders within lines, the numbers are read back on the boxes
+    let mut c = Board::new();
+    c.read_grid(&b.print(true)).unwrap();
+    assert!(!c.has_regions());
+    assert_eq!(c.print(false), b.print(false));
+}
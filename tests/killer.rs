@@ -0,0 +1,132 @@
+use sudoku::Board;
+use sudoku::Cage;
+use sudoku::Candidates;
+use sudoku::Field;
+use sudoku::Format;
+use sudoku::Metadata;
+use sudoku::ParseError;
+use sudoku::Technique;
+
+// solution 1234 / 3412 / 2143 / 4321
+const KILLER: &str = "\
+AABB
+CDDB
+CEEF
+GGFF
+
+A=3 B=9 C=5 D=5 E=5 F=6 G=7
+";
+
+fn read(input: &str) -> Result<Board, ParseError> {
+    let mut b = Board::new();
+    b.read_format(input, Format::Killer).map(|_| b)
+}
+
+fn candidates(values: &[usize]) -> Candidates {
+    values.iter().copied().collect()
+}
+
+fn options(b: &Board, idx: usize) -> Option<Candidates> {
+    match b.iter().nth(idx) {
+        Some(Field::OptionList(list)) => Some(*list),
+        _ => None,
+    }
+}
+
+#[test]
+fn sum_combinations() {
+    assert_eq!(Cage::combinations(9, 2, 3), vec!(candidates(&[0, 1])));
+    assert_eq!(Cage::combinations(9, 2, 17), vec!(candidates(&[7, 8])));
+    assert_eq!(Cage::combinations(9, 2, 10), vec!(candidates(&[0, 8]), candidates(&[1, 7]), candidates(&[2, 6]), candidates(&[3, 5])));
+    assert_eq!(Cage::combinations(9, 9, 45), vec!(Candidates::full(9)));
+    assert!(Cage::combinations(9, 2, 2).is_empty());
+    assert!(Cage::combinations(9, 2, 18).is_empty());
+    assert!(Cage::combinations(4, 5, 10).is_empty());
+}
+
+#[test]
+fn sums_prune_options() {
+    let mut b = read(KILLER).unwrap();
+    assert_eq!(b.count_solutions(2), 1);
+    assert!(!b.clone().enter(0, 1, 2));
+
+    // reading and entering numbers leave the options alone, the cage combinations are a step of their own
+    assert_eq!(options(&b, 0), Some(Candidates::full(4)));
+    let step = b.hint().unwrap();
+    assert_eq!(step.technique, Technique::CageCombination);
+    assert_eq!(step.cells, vec!(0, 1));                            // A: 1 + 2
+    assert_eq!(step.eliminations, vec!((0, 2), (0, 3), (1, 2), (1, 3)));
+    assert!(b.apply_step(&step));
+    assert_eq!(options(&b, 0), Some(candidates(&[0, 1])));
+
+    // the combinations left by a number narrow its mates
+    assert!(b.enter(0, 0, 0));
+    assert_eq!(options(&b, 1), Some(candidates(&[1])));
+    assert!(b.enter(0, 1, 1));
+    let step = b.hint().unwrap();
+    assert_eq!(step.technique, Technique::CageCombination);
+    assert_eq!(step.cells, vec!(2, 3, 7));                         // B: 2 + 3 + 4
+    assert!(b.apply_step(&step));
+    assert_eq!(options(&b, 2), Some(candidates(&[2, 3])));         // 2 is taken in the line
+    assert_eq!(options(&b, 7), Some(candidates(&[1, 2, 3])));
+}
+
+#[test]
+fn invalid_cages_are_rejected() {
+    let mut b = Board::new();
+    b.read(&".".repeat(16)).unwrap();
+    assert_eq!(b.clone().set_cages(vec!(Cage::new(vec!(0, 1), 8))), Err(ParseError::InvalidCage { cage: 0, size: 2, sum: 8 }));
+    assert_eq!(b.clone().set_cages(vec!(Cage::new(vec!(0, 16), 3))), Err(ParseError::InvalidCage { cage: 0, size: 2, sum: 3 }));
+    assert_eq!(b.clone().set_cages(vec!(Cage::new(vec!(0, 1), 3), Cage::new(vec!(1, 2), 5))), Err(ParseError::OverlappingCages { cell: (0, 1) }));
+}
+
+#[test]
+fn parse_errors() {
+    assert_eq!(read(&KILLER.replace(" G=7", "")).err(), Some(ParseError::InvalidToken { position: 15, token: "G".to_string() }));
+    assert_eq!(read(&KILLER.replace("G=7", "G=7 Z=4")).err(), Some(ParseError::InvalidToken { position: 49, token: "Z=4".to_string() }));
+    assert_eq!(read(&KILLER.replace("A=3", "A=x")).err(), Some(ParseError::InvalidToken { position: 21, token: "A=x".to_string() }));
+    assert_eq!(read(&KILLER.replace("A=3", "A=8")).err(), Some(ParseError::InvalidCage { cage: 0, size: 2, sum: 8 }));
+    assert_eq!(read(&KILLER.replace("CDDB", "CDDBB")).err(), Some(ParseError::WrongRowLength { line: 1, length: 5, valid: vec!(4) }));
+    assert_eq!(read(&format!("{}\n11..\n....\n....\n....\n", KILLER)).err(), Some(ParseError::ConflictingGivens { cell: (0, 1), peer: (0, 0), value: 1 }));
+
+    // a given can break a sum without conflicting with any other number
+    assert_eq!(read(&format!("{}\n3...\n....\n....\n....\n", KILLER)).err(), Some(ParseError::InvalidCage { cage: 0, size: 2, sum: 3 }));
+}
+
+#[test]
+fn killer_files_round_trip() {
+    let mut metadata = Metadata::new();
+    metadata.push('A', "someone");
+    let mut b = read(&format!("{}\n1...\n....\n....\n...1\n", KILLER)).unwrap();
+    b.enter(1, 1, 3);
+    let text = b.print_format(Format::Killer, &metadata);
+
+    let mut c = Board::new();
+    assert_eq!(c.read_format(&text, Format::Killer), Ok(metadata.clone()));
+    assert_eq!(c.cages(), b.cages());
+    assert_eq!(c.print(false), b.print(false));
+    assert_eq!(c.print_format(Format::Killer, &metadata), text);
+}
+
+#[test]
+fn generated_killers_are_unique_without_single_fields() {
+    for base in [2, 3] {
+        let b = Board::generate_killer(base);
+        assert!(b.has_unique_solution());
+        let solution = b.clone().solve().unwrap();
+        let numbers: Vec<usize> = solution.iter().map(|f| match f {
+            Field::Number(n) => *n,
+            Field::OptionList(_) => panic!("unsolved field"),
+        }).collect();
+
+        let mut cells: Vec<usize> = b.cages().iter().flat_map(|cage| cage.cells.clone()).collect();
+        cells.sort();
+        assert_eq!(cells, (0..numbers.len()).collect::<Vec<_>>());
+        for cage in b.cages() {
+            assert!(cage.cells.len() > 1);
+            let values: Candidates = cage.cells.iter().map(|&idx| numbers[idx]).collect();
+            assert_eq!(values.len(), cage.cells.len());
+            assert_eq!(cage.cells.iter().map(|&idx| numbers[idx] + 1).sum::<usize>(), cage.sum);
+        }
+    }
+}
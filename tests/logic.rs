@@ -0,0 +1,198 @@
+use sudoku::Board;
+use sudoku::Field;
+use sudoku::Format;
+use sudoku::House;
+use sudoku::Step;
+use sudoku::Technique;
+
+// Every fixture is the candidate grid of a puzzle at the point where its technique is the easiest one left.
+
+fn step(technique: Technique, cells: Vec<usize>, houses: Vec<House>, placement: Option<(usize, usize)>, eliminations: Vec<(usize, usize)>) -> Step {
+    Step { technique, cells, houses, placement, eliminations }
+}
+
+fn numbers(b: &Board) -> Vec<Option<usize>> {
+    b.iter().map(|f| match f {
+        Field::Number(n) => Some(*n),
+        Field::OptionList(_) => None,
+    }).collect()
+}
+
+fn assert_consistent(step: &Step, solution: &Board) {  // the step neither places nor removes anything against the solution
+    let numbers = numbers(solution);
+    if let Some((idx, val)) = step.placement {
+        assert_eq!(numbers[idx], Some(val), "{:?}", step);
+    }
+    for &(idx, val) in step.eliminations.iter() {
+        assert_ne!(numbers[idx], Some(val), "{:?}", step);
+    }
+}
+
+fn assert_next_step(b: &Board, expected: Step) {
+    assert_eq!(b.hint(), Some(expected.clone()));
+    for solution in b.solutions().take(20) {
+        assert_consistent(&expected, &solution);
+    }
+}
+
+fn assert_next(candidates: &str, expected: Step) {
+    let mut b = Board::new();
+    b.read_pencil_marks(candidates).unwrap();
+    assert_next_step(&b, expected);
+}
+
+#[test]
+fn naked_single() {
+    let mut b = Board::new();
+    b.read(&format!("12345678.{}", ".".repeat(72))).unwrap();
+    assert_next_step(&b, step(Technique::NakedSingle, vec!(8), vec!(House::Line(0), House::Col(8), House::Quad(2)), Some((8, 8)), vec!()));
+}
+
+#[test]
+fn cage_combination() {
+    // 7 in two fields: 7, 8 and 9 are too large, with 1 and 6 entered in the column of the first, 1 + 6 is left out of the second
+    let input = "\
+AA.......
+.........
+.........
+.........
+.........
+.........
+.........
+.........
+.........
+
+A=7
+";
+    let mut b = Board::new();
+    b.read_format(input, Format::Killer).unwrap();
+    assert!(b.enter(4, 0, 5) && b.enter(7, 0, 0));
+    assert_next_step(&b, step(Technique::CageCombination, vec!(0, 1), vec!(), None, vec!((0, 6), (0, 7), (0, 8), (1, 0), (1, 5), (1, 6), (1, 7), (1, 8))));
+}
+
+#[test]
+fn hidden_single() {
+    assert_next(
+        ".23.56.89.23456.89..3456.8...345.7.91..........345.7...23.56.8..2.456.89..34.6.8...3.56.89..3456.89..3456.8...345...9..345...9.2.......1.3.56.8.......7..1.34.6.8.......7...2345...91...............8......6.....345.....23.5.....2.45...9..34......23.56....23.567....3.567...23.567.........8.1...........4......2..56...........9123.56.89123456789..345678..234567.9.2345.7.9..34567..123.5678.12..56.8.1.3..678.123.56.89123456789..345678..234567.9.2345.7.9..34567..123.5678.12..56.8.1.3..678.1...56.8.1...5678.....5678.1..4567.....45.7.....45678.........9..3.......2.......1.3..6.8.1.3..678..2.......1.34.67....34..7..........91....678.1..4.6.8.....5.......4.....1.3.5678.........9123.567...23.5.7....3.5678.1....678.1....6.8.1....678.",
+        step(Technique::HiddenSingle, vec!(70), vec!(House::Quad(8)), Some((70, 3)), vec!()),
+    );
+}
+
+#[test]
+fn pointing_pair() {
+    assert_next(
+        "......7..1.3....8.1.3....891.3....89....5.......4..........6.....3.....9.2.......1.3456...1.34.....1.3456..91.3..6..91.3.....9.2.............7....34....9.......8..234.6.8..234...8..234.6.89..3..6.89..3....89......7..1..........34....9....5............9.....6...123...78....4.....123...78.1.3....8..23....8.....5......3...7..1234...8.....5....1234..78.123...789123...7891.3....89.234...8......67....34.67...234...8..234...8..234..78.....5.....23...78......6...........91..........34..7..12345..........7..12345....123...........6...1.3.5......345...........8.........91.3.56.8.........91.3.56.8.1.3...78....4.....1.3.5..8...3.5.....2.........3..67...23456.8..234...8..23456.8..23...789.23...789..3.5..89..345.........67..1........",
+        step(Technique::PointingPair, vec!(60, 78), vec!(House::Quad(8), House::Col(6)), None, vec!((42, 3))),
+    );
+}
+
+#[test]
+fn box_line_reduction() {
+    assert_next(
+        "...4.6.8....4.6.89.....6.89......7...2.......1..4....9..3..........5....1.......91..........34..7.9..3.5...9.......8.....5...9...45...9.2...67...2...67.9......7.9.2.............7.9....5...9..3...........6...1...5...91.....7.....4............8.......7......5.....23..6.8912...6..91.3........3..6...1....6.8.1.3..6.89...4.......3..6.8...3..6.89...4.....1...56..91.3.5..........7..1...56.8.1.3..6.89.2.........3..6...1.........23..6..9.2..56..9...4............8.....567....3..67.9..3...7.9..34...8...34...8.......7..1...5....1.3.5..8..2...............91.3....8......6...........9.23..6.8.1.3..6.8.1....6.........7....3..6......4.....123....8.....5........5.....23..6.8.1.3..6.8....4.....1.3....89..3..6..912....78.123...78.1.3...7..",
+        step(Technique::BoxLineReduction, vec!(14, 23), vec!(House::Col(5), House::Quad(1)), None, vec!((13, 4))),
+    );
+}
+
+#[test]
+fn naked_pair() {
+    assert_next(
+        "1.3.......2...6.8.1.3.........45.....2.....8.........9......7...2.45........56......4......2.....8.......7.91...5.........6.........78.1.......9.2..5......3..........5.....2...6.........7.91..4......2....7....3.........4.6..........8.1....6..9......78...3..........5.........6......4...........78..2......91.........2......91.....78....4..7..1..4.............9....5.78..2............6.8...3..........56....2...............9.....6.....3..........5..8.1...........4...8....45..........7....3...7.....4..7....34............8.........9....5....12............6...12...............9....5...........8..2.......1.............6.....3............7.....4..........6...1.........2.............7....3.........4.........5............9.......8.",
+        step(Technique::NakedPair, vec!(8, 44), vec!(House::Col(8)), None, vec!((26, 5))),
+    );
+}
+
+#[test]
+fn x_wing() {
+    assert_next(
+        "...4...89....5.......4....9..3...........6....2.............7..1...........4...8..2.............7..1......8.1...5..8....4.........5..8.........9..3...........6...1..4...8...3...........6...1......8.........9......7.....4...8..2...........5........5.........6...1......8.......7....3.........4.....12.....8.........912..........4...8..2.........3..............9....5....1.............6.........7.....4...8.1..4....9...4....9......7...2..............8......6...1.34.........5....1.3...........6...1......8..2..5........5..8.......7....3.5..8.123.........4.............9......7..1......89.2..5...9...4.....12.........3.5..8.123...........6...123........3......1..4......2.4..........6...12...............9....5...........8.......7..",
+        step(Technique::XWing, vec!(0, 8, 36, 44), vec!(House::Line(0), House::Line(4), House::Col(0), House::Col(8)), None, vec!((18, 7))),
+    );
+}
+
+#[test]
+fn hidden_pair() {
+    assert_next(
+        "..34.67....3456......4.6.8.1...........456..9....5..8....4..7....3.5...9.2.........34.6.....3456...........9.2..........456.........7..1..........3.5...........8.1..4..7...2.......1..4...8....4...8....45...9..3.........4..7......5...9.....6....2......91....6.....3...........6.8.1...5.....2..5..8.....5...9...4...........7..1....6.........7......5.......4.6...1..4.............9.2..............8...3.......2.4....9.......8..2.4.......3............7...2..5........5...9.....6...1............5....1..4.6...1..4.6.........7....3......1..4............8..2...............91234.....1.34.....12.4.............9.......8.1..4..........6.........7......5...........8.........9......7......5.....2............6.....3......1...........4.....",
+        step(Technique::HiddenPair, vec!(22, 25), vec!(House::Line(2)), None, vec!((22, 3))),
+    );
+}
+
+#[test]
+fn naked_triple() {
+    assert_next(
+        "1.3.............8.........9...4.........5.....2.......1.3..6...1.3..6.........7..12....7..12..5.........6.........7.91.......9..3.........4.....1...5...........8.1.34..7..1..45......34..7........78.1....6........6.8.1.3.5............9.2...........5.....2...6....23....8.1..............7..........9.23..6.8...3..6.8....4.....1234...8.......7...234...8..23.5.....234.6......456...123.56.8.1.3.56.8.........9........912.4.6....234......23.5...........8....456...123.56.........7....3.5.........6...........9.2.4..78..23.5..8..234........45..8...3...78...34...8.1...........4..78...3..........5...........89...4....91..............789.2............6....2.4...8..2.4.....1.............6....234....9......7....3.5..89..345..8...3.5....",
+        step(Technique::NakedTriple, vec!(12, 21, 66), vec!(House::Col(3)), None, vec!((57, 7))),
+    );
+}
+
+#[test]
+fn swordfish() {
+    assert_next(
+        "1...5..8.....5..8..2..........4...........7....3...........6...........91......8.1.....7.......6.....3..............9.2..............8....4.....1.....7......5..........78.........9...4.........56.......56...1..........3............78..2..........4...........7.......6.8...3......1....6.......5....1......8..2...............9..3.......2............6.8.1....6...........9......7......5.......4.....1......8.........91............5...........8....4......2.............7.......6.....3..........5..8....4.....1..............7......5..8.........9.2.........3...........6........6.......5..8.........9.2.........3.........4.....1......8.1...5..8.......7...2.........3............7..1...5....1...5..8......6...........9....5..8....4.....",
+        step(Technique::Swordfish, vec!(18, 25, 54, 58, 76, 79), vec!(House::Line(2), House::Line(6), House::Line(8), House::Col(0), House::Col(4), House::Col(7)), None, vec!((0, 7), (70, 7))),
+    );
+}
+
+#[test]
+fn hidden_triple() {
+    assert_next(
+        "...4.....1............5.78.....56.........78.....567.9..3..6.89..3..6.89.2............6...........9......78..23.......2....78...3...7......5....1...........4.......3.5......3....8..2.......1...........4.........56..9.....6.89.....6.89......7....3...7.9.2.......1......8...34...89.....6.....34..78.1.3...78...3...78.....5........5.7.9..3....8.....56....2......9.2....7..1.........2...67.....4.......3....8...3...7.....4.....1....6.8..23....8.....5......3...78.123..678...3..678.........9.......8.......7..........9....56.....3..........56......4......2.......1........1.............6......4...........7..........9.2.........3....8.....5......3....8..2...........5......3.........4...8.1...........4...8.......7.9......7.9.....6...",
+        step(Technique::HiddenTriple, vec!(5, 23, 59), vec!(House::Col(5)), None, vec!((5, 6))),
+    );
+}
+
+#[test]
+fn xy_wing() {
+    assert_next(
+        "1..4........45......3.......2..........4..78.........91.....7......5..8......6....2..........456...1..4.6.....3...7.....4..78...3....8.1.....7......5..8.........9........9......7.........8.....5....1.............6....2..........4.......3........3......1............5.......4..........6.........7.........8.........9.2..........4.67...2..........4.6..........8.........9....5......3......1...........4..7.....4..78....4...8.........91..........3.......2...........5.........6......4..7..1..4...8...3......1..4..........6....2.......1......8.........9......7......5........5...........89.2.............7.9......78....4..........6.....3......1........1....6........6..9......7....3.....9....5....1.3.........4......2..............8.",
+        step(Technique::XYWing, vec!(56, 38, 72), vec!(House::Col(2), House::Quad(6)), None, vec!((36, 5))),
+    );
+}
+
+#[test]
+fn naked_quad() {
+    assert_next(
+        "12..5.....2..5.........6.........7.9.......8.....5.7.9...4.....1.....7....3......1...5...........8.........9..3.........4.........5.7...2.......1.....7.......6.....34...........7....34.....12...6...12...6...1....6.......5............9.......8...3.5.7......56..........8..2...67...23..6........67..........9...4.....1..........34..7.91..........34........4.67.9..3..6........6789..3..6.8.....5.....2.........34....9...4.6....2.......1..4....9....5....1......89..3..6.8......6.8.......7.......6.....3......1...............8.........9...4...........7...2...........5.....2.4......2.4...........7......5....1....6.....3......1....6.8......6.8.........9.......8.........9....5....1....6.........7...2.......1....6.....3.........4.....",
+        step(Technique::NakedQuad, vec!(30, 31, 32, 40), vec!(House::Quad(4)), None, vec!((39, 5), (39, 6), (41, 5), (41, 6))),
+    );
+}
+
+#[test]
+fn hidden_quad() {
+    assert_next(
+        "1..45.........6...1..45........5..8..2...............9..3............7..1...5..8........8...3.5...91.3.5.7.9..3.5......34.6......45.7..1...5...9.2.......1...56..9.2.........3.5...9..3.5.7.91..........3..6.......5.78.....5..89.....6..9...4.....1.......9.2.4....912......9.....6..........8...3.........4..7.9....5..........7.9......7....345......3.5........5...91..4....91..45.........6..........8..2...........5...9.......8......6....2.............7.....45.......4....91..........3........34.6..91...........4...89......7......5.....2..............89..34.6..9.....6.89..3.5...9.2..5...9.2..5..89...4.....1.......9.....6...12..5.789..3.....91...5.789...456..9......7...2.45...9..3....891.3.....91......8.12..5...9...4.6..91...56..9",
+        step(Technique::HiddenQuad, vec!(69, 71, 78, 80), vec!(House::Quad(8)), None, vec!((69, 7), (69, 8), (71, 7), (71, 8), (78, 8), (80, 5), (80, 8))),
+    );
+}
+
+#[test]
+fn simple_coloring() {
+    assert_next(
+        "......7..1.3....8...3....891.3....89....5.......4..........6.....3.....9.2.........3456...1.34.......3456..91.3..6..91.3.....9.2.............7....34....9.......8..234.6.8..234...8..234.6.89..3..6.89..3....89......7..1..........34....9....5............9.....6...123...78....4.....123...78.1.3....8..2.....8.....5......3...7..1234...8.....5....1234..78.123...789123...7891.3....89.2.....8......67....34.67...234...8..234...8..234..78.....5.....23...78......6...........91..........34..7..12345..........7..12345....123...........6...1.3.5......345...........8.........91.3.56.8.........91.3.56.8.1.3...78....4.....1.3.5..8...3.5.....2............67...23456.8..234...8..23456.8..23...789.23...789..3.5..89..345.........67..1........",
+        step(Technique::SimpleColoring, vec!(66, 71, 79, 43), vec!(House::Line(7), House::Quad(8), House::Col(7)), None, vec!((39, 6))),
+    );
+}
+
+#[test]
+fn logical_solving_agrees_with_brute_force() {
+    let mut x = Board::generate_diagonal(3);
+    x.reset_to_start();
+    let mut puzzles = vec!(Board::generate_unique(3), Board::generate_unique(3), x, Board::generate_random_jigsaw(3), Board::generate_killer(3));
+    let mut escargot = Board::new();
+    escargot.read("1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..").unwrap();
+    puzzles.push(escargot);
+
+    for b in puzzles {
+        let solution = b.clone().solve().unwrap();
+        assert!(b.has_unique_solution());
+        let logical = b.solve_logical();
+        for step in logical.steps.iter() {
+            assert_consistent(step, &solution);
+        }
+        let expected = numbers(&solution);
+        for (idx, n) in numbers(&logical.board).into_iter().enumerate() {
+            assert!(n.is_none() || n == expected[idx]);
+        }
+    }
+}
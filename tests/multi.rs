@@ -0,0 +1,107 @@
+use sudoku::Field;
+use sudoku::MultiBoard;
+use sudoku::MultiLayout;
+use sudoku::ParseError;
+
+fn read(layout: MultiLayout, input: &str) -> Result<MultiBoard, ParseError> {
+    let mut b = MultiBoard::new(layout);
+    b.read(input).map(|_| b)
+}
+
+fn error(layout: MultiLayout, input: &str) -> Option<ParseError> {
+    MultiBoard::new(layout).read(input).err()
+}
+
+fn number(b: &MultiBoard, grid: usize, line: usize, col: usize) -> Option<usize> {  // canvas coordinates
+    let (l, c) = b.layout().offsets()[grid];
+    match b.grids()[grid].iter().nth((line - l) * 9 + col - c) {
+        Some(Field::Number(n)) => Some(*n),
+        _ => None,
+    }
+}
+
+fn covering(layout: MultiLayout, line: usize, col: usize) -> Vec<usize> {  // grids covering a canvas field
+    (0..layout.offsets().len()).filter(|&g| {
+        let (l, c) = layout.offsets()[g];
+        (l..l + 9).contains(&line) && (c..c + 9).contains(&col)
+    }).collect()
+}
+
+fn assert_solved(b: &MultiBoard) {  // every grid is complete and shared fields hold the same number in all grids
+    let size = b.layout().size();
+    for grid in b.grids() {
+        assert!(grid.iter().all(|f| matches!(f, Field::Number(_))));
+        assert_eq!(grid.count_solutions(2), 1);
+    }
+    for line in 0..size {
+        for col in 0..size {
+            let numbers: Vec<Option<usize>> = covering(b.layout(), line, col).into_iter().map(|g| number(b, g, line, col)).collect();
+            assert!(numbers.windows(2).all(|w| w[0] == w[1]), "r{}c{}", line + 1, col + 1);
+        }
+    }
+}
+
+#[test]
+fn layouts_round_trip_and_solve() {
+    for layout in MultiLayout::ALL {
+        assert_eq!(MultiLayout::from_name(layout.name()), Some(layout));
+
+        let b = MultiBoard::generate(layout);
+        let text = b.print();
+        assert_eq!(text.lines().count(), layout.size());
+        let c = read(layout, &text).unwrap();
+        assert_eq!(c.print(), text);
+
+        assert!(c.has_unique_solution());
+        let solution = c.solve().unwrap();
+        assert_solved(&solution);
+        assert!(!solution.print().contains('.'));
+        for (given, solved) in text.chars().zip(solution.print().chars()) {
+            assert!(given == '.' || given == solved);
+        }
+    }
+}
+
+#[test]
+fn shared_fields_are_solved_in_every_grid() {
+    // a single given in the shared box of a twin is placed in both grids
+    let mut canvas: Vec<String> = (0..15).map(|line| if line < 6 { ".".repeat(9) } else if line < 9 { ".".repeat(15) } else { format!("      {}", ".".repeat(9)) }).collect();
+    canvas[7].replace_range(7..8, "5");
+    let b = read(MultiLayout::Twin, &canvas.join("\n")).unwrap();
+    assert_eq!(number(&b, 0, 7, 7), Some(4));
+    assert_eq!(number(&b, 1, 7, 7), Some(4));
+    let solution = b.solve().unwrap();
+    assert_solved(&solution);
+    assert_eq!(b.count_solutions(2), 2);
+}
+
+#[test]
+fn inconsistent_overlap_has_no_solution() {
+    // both grids of a twin are solvable on their own, but the second one is relabelled and
+    // leaves a different number in every field of the shared box
+    let solution = MultiBoard::generate(MultiLayout::Twin).solve().unwrap();
+    let lines: Vec<Vec<char>> = solution.print().lines().map(|l| l.chars().collect()).collect();
+    let canvas: Vec<String> = lines.iter().enumerate().map(|(line, chars)| chars.iter().enumerate().map(|(col, &c)| {
+        match covering(MultiLayout::Twin, line, col).as_slice() {
+            [0, 1] => '.',
+            [1] => char::from(b'1' + (c as u8 - b'1' + 1) % 9),
+            _ => c,
+        }
+    }).collect()).collect();
+
+    let b = read(MultiLayout::Twin, &canvas.join("\n")).unwrap();
+    for grid in b.grids() {
+        assert_eq!(grid.count_solutions(2), 1);
+    }
+    assert!(b.solve().is_none());
+    assert_eq!(b.count_solutions(2), 0);
+}
+
+#[test]
+fn canvas_errors() {
+    let empty = MultiBoard::new(MultiLayout::Twin).print();
+    assert_eq!(error(MultiLayout::Twin, &empty.replacen("..", "11", 1)), Some(ParseError::ConflictingGivens { cell: (0, 1), peer: (0, 0), value: 1 }));
+    assert_eq!(error(MultiLayout::Twin, &empty.replacen(".........\n", ".........1\n", 1)), Some(ParseError::InvalidToken { position: 9, token: "1".to_string() }));
+    assert_eq!(error(MultiLayout::Twin, &empty.replacen(".", "x", 1)), Some(ParseError::InvalidToken { position: 0, token: "x".to_string() }));
+    assert_eq!(error(MultiLayout::Samurai, &empty), Some(ParseError::WrongRowCount { rows: 15, expected: 21 }));
+}
@@ -0,0 +1,81 @@
+use sudoku::Board;
+use sudoku::Field;
+use sudoku::Origin;
+
+const ESCARGOT: &str = "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..";
+
+fn escargot() -> Board {
+    let mut b = Board::new();
+    b.read(ESCARGOT).unwrap();
+    b
+}
+
+fn origins(b: &Board) -> Vec<Option<Origin>> {
+    let line_size = b.shape().line_size();
+    (0..line_size * line_size).map(|idx| b.origin(idx / line_size, idx % line_size)).collect()
+}
+
+#[test]
+fn read_numbers_are_given_and_solved_ones_derived() {
+    let b = escargot();
+    let expected: Vec<Option<Origin>> = ESCARGOT.chars().map(|c| if c == '.' { None } else { Some(Origin::Given) }).collect();
+    assert_eq!(origins(&b), expected);
+    assert_eq!(b.origin(9, 0), None);
+
+    let solved = b.clone().solve().unwrap();
+    for (idx, c) in ESCARGOT.chars().enumerate() {
+        assert_eq!(origins(&solved)[idx], Some(if c == '.' { Origin::Derived } else { Origin::Given }));
+    }
+
+    // numbers placed by a hint are derived as well
+    let mut c = b.clone();
+    let step = c.hint().unwrap();
+    let (idx, _) = step.placement.unwrap();
+    assert!(c.apply_step(&step));
+    assert_eq!(origins(&c)[idx], Some(Origin::Derived));
+}
+
+#[test]
+fn givens_are_locked() {
+    let mut b = escargot();
+    assert!(!b.enter(0, 0, 1));
+    assert!(!b.erase(0, 0));
+    assert!(matches!(b.iter().next(), Some(Field::Number(0))));
+
+    // entered numbers may be replaced and erased
+    assert!(b.enter(0, 1, 7));
+    assert_eq!(b.origin(0, 1), Some(Origin::Entered));
+    assert!(b.enter(0, 1, 5));
+    assert!(matches!(b.iter().nth(1), Some(Field::Number(5))));
+    assert!(b.erase(0, 1));
+    assert_eq!(b.origin(0, 1), None);
+    assert!(!b.erase(0, 1));
+    assert_eq!(b.print(false), escargot().print(false));
+}
+
+#[test]
+fn reset_to_start_keeps_the_givens() {
+    let mut b = escargot();
+    assert!(b.enter(0, 1, 7));
+    let step = b.hint().unwrap();
+    assert!(b.apply_step(&step));
+    assert_eq!(b.start().print(false), escargot().print(false));
+    assert_eq!(origins(&b.start()), origins(&escargot()));
+
+    b.reset_to_start();
+    assert_eq!(b.print(false), escargot().print(false));
+    assert_eq!(b.print_candidates(), escargot().print_candidates());
+    assert_eq!(origins(&b), origins(&escargot()));
+
+    let solved = escargot().solve().unwrap();
+    assert_eq!(solved.start().print(false), escargot().print(false));
+}
+
+#[test]
+fn generated_numbers_are_given() {
+    let b = Board::generate_unique(3);
+    assert!(origins(&b).iter().zip(b.iter()).all(|(origin, field)| match field {
+        Field::Number(_) => *origin == Some(Origin::Given),
+        Field::OptionList(_) => origin.is_none(),
+    }));
+}
@@ -0,0 +1,88 @@
+use sudoku::Board;
+use sudoku::Field;
+use sudoku::ParseError;
+use sudoku::Shape;
+
+const ESCARGOT: &str = "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..";
+
+fn escargot() -> Board {
+    let mut b = Board::new();
+    b.read(ESCARGOT).unwrap();
+    b
+}
+
+fn with_eliminations(mut b: Board, steps: usize) -> Board {  // candidates that no longer follow from the numbers alone
+    // single candidates are read back as numbers, so they are placed before printing
+    let single = |b: &Board| b.iter().any(|f| matches!(f, Field::OptionList(list) if list.len() == 1));
+    for step in 0.. {
+        if step >= steps && !single(&b) {
+            break;
+        }
+        let Some(step) = b.hint() else { break };
+        assert!(b.apply_step(&step));
+    }
+    b
+}
+
+fn read(input: &str) -> Result<Board, ParseError> {
+    let mut b = Board::new();
+    b.read_pencil_marks(input).map(|_| b)
+}
+
+fn assert_round_trip(b: &Board) {
+    let wide = read(&b.print_pencil_marks()).unwrap();
+    assert_eq!(wide.print_candidates(), b.print_candidates());
+    assert_eq!(wide.print_pencil_marks(), b.print_pencil_marks());
+
+    let compact = read(b.print_candidates().trim()).unwrap();
+    assert_eq!(compact.print_candidates(), b.print_candidates());
+    assert_eq!(compact.print(false), wide.print(false));
+}
+
+#[test]
+fn pencil_marks_round_trip() {
+    assert_round_trip(&escargot());
+    assert_round_trip(&with_eliminations(escargot(), 12));
+    assert_round_trip(&with_eliminations(Board::generate_unique(Shape::new(2, 3)), 4));
+    assert_round_trip(&with_eliminations(Board::generate(4), 4));
+    assert_eq!(escargot().print_candidates().trim().len(), 729);
+}
+
+#[test]
+fn compact_4x4_marks_have_the_length_of_8x8_puzzles() {
+    // one-line puzzles are read as 8x8 boards, the CLI needs -P for these marks
+    let marks = "1234.2....3....4..3.12341....2...2..1......4..3....4..3..2..1...";
+    assert_eq!(Board::new().read(marks), Err(ParseError::ConflictingGivens { cell: (0, 5), peer: (0, 1), value: 2 }));
+
+    let b = read(marks).unwrap();
+    assert_eq!(b.shape().line_size(), 4);
+    assert_eq!(b.count_solutions(2), 1);
+}
+
+#[test]
+fn single_candidates_are_read_as_numbers() {
+    let b = read(escargot().print_candidates().trim()).unwrap();
+    assert_eq!(b.print(false), escargot().print(false));
+    assert!(matches!(b.iter().next(), Some(Field::Number(0))));
+
+    // the number is removed from the candidates of its peers
+    let marks = escargot().print_candidates();
+    let b = read(&format!("{}.2.......{}", &marks[..9], marks[18..].trim())).unwrap();
+    assert!(matches!(b.iter().nth(1), Some(Field::Number(1))));
+    assert!(matches!(b.iter().nth(2), Some(Field::OptionList(list)) if !list.contains(1)));
+}
+
+#[test]
+fn parse_errors() {
+    let marks = escargot().print_candidates();
+    let marks = marks.trim();
+    assert!(matches!(read(&marks[1..]), Err(ParseError::WrongLength { length: 728, .. })));
+    assert_eq!(read(&format!("{}3{}", &marks[..10], &marks[11..])).err(), Some(ParseError::InvalidToken { position: 10, token: "3".to_string() }));
+
+    let wide = escargot().print_pencil_marks();
+    assert_eq!(read(&wide.replacen("| 457   3 ", "| 457 3 3 ", 1)).err(), Some(ParseError::WrongRowLength { line: 2, length: 10, valid: vec!(9) }));
+    let position = wide.find("2568").unwrap() + 2;
+    assert_eq!(read(&wide.replacen("2568", "25x8", 1)).err(), Some(ParseError::InvalidToken { position, token: "x".to_string() }));
+    let short: String = wide.lines().take(10).map(|l| format!("{}\n", l)).collect();
+    assert_eq!(read(&short).err(), Some(ParseError::WrongRowCount { rows: 7, expected: 9 }));
+}
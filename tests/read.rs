@@ -0,0 +1,69 @@
+use sudoku::Board;
+use sudoku::ParseError;
+
+const ESCARGOT: &str = "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..";
+
+fn read(input: &str) -> Result<(), ParseError> {
+    Board::new().read(input)
+}
+
+#[test]
+fn wrong_lengths_list_the_valid_ones() {
+    let Err(ParseError::WrongLength { length, valid }) = read(&ESCARGOT[1..]) else {
+        panic!("80 characters were read");
+    };
+    assert_eq!(length, 80);
+    assert!(valid.contains(&16) && valid.contains(&81) && valid.contains(&512));
+    assert!(!valid.contains(&80));
+    assert!(matches!(read(""), Err(ParseError::WrongLength { length: 0, .. })));
+}
+
+#[test]
+fn errors_point_at_the_token() {
+    assert_eq!(read(&ESCARGOT.replacen('.', "x", 1)), Err(ParseError::InvalidToken { position: 1, token: "x".to_string() }));
+    assert_eq!(read(&format!("....17{}", "..".repeat(253))), Err(ParseError::ValueOutOfRange { position: 4, value: 17, max: 16 }));
+
+    // the later of two equal numbers in a house is reported with its peer
+    assert_eq!(read(&ESCARGOT.replacen("1....7", "1....1", 1)), Err(ParseError::ConflictingGivens { cell: (0, 5), peer: (0, 0), value: 1 }));
+    assert_eq!(read(&ESCARGOT.replacen(".3.", ".1.", 1)), Err(ParseError::ConflictingGivens { cell: (1, 1), peer: (0, 0), value: 1 }));
+}
+
+#[test]
+fn messages_count_from_one() {
+    assert!(read("123").unwrap_err().to_string().starts_with("input has 3 characters, expected one of 16, 36, 64, 81,"));
+    assert_eq!(read(&ESCARGOT.replacen('.', "x", 1)).unwrap_err().to_string(), "invalid token 'x' at position 2");
+    assert_eq!(read(&format!("17{}", "..".repeat(255))).unwrap_err().to_string(), "value 17 at position 1 is out of range 1-16");
+    assert_eq!(read(&ESCARGOT.replacen("1....7", "1....1", 1)).unwrap_err().to_string(), "value 1 at r1c6 conflicts with r1c1");
+}
+
+
+#[test]
+fn bad_two_digit_tokens_are_errors() {
+    let rest = "..".repeat(255);
+    assert_eq!(read(&format!("00{}", rest)), Err(ParseError::ValueOutOfRange { position: 0, value: 0, max: 16 }));
+    for token in [" 1", "-1", "+1", "1.", ".1", "1x"] {
+        assert_eq!(read(&format!("{}{}", token, rest)), Err(ParseError::InvalidToken { position: 0, token: token.to_string() }), "{}", token);
+    }
+
+    // characters are counted, not bytes, so tokens may hold any of them
+    assert_eq!(read(&format!("1é{}", rest)), Err(ParseError::InvalidToken { position: 0, token: "1é".to_string() }));
+    assert_eq!(read(&format!("..１２{}", "..".repeat(254))), Err(ParseError::InvalidToken { position: 2, token: "１２".to_string() }));
+}
+
+#[test]
+fn untrusted_input_never_panics() {
+    let symbols = ["0", "00", "9", "99", "x", "é", "١", "１", "\u{0}", " ", "-", "\u{1F600}"];
+    for length in [16, 81, 100, 256, 512, 625, 1296, 2592] {
+        for symbol in symbols {
+            for position in [0, 1, length / 2, length - 1] {
+                let mut input: Vec<String> = vec!(".".to_string(); length);
+                input[position] = symbol.to_string();
+                let input: String = input.concat();
+                let _ = read(&input);
+                let _ = Board::new().read_grid(&input);
+                let _ = Board::new().read_pencil_marks(&input);
+            }
+            let _ = read(&symbol.repeat(length));
+        }
+    }
+}
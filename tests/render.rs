@@ -0,0 +1,90 @@
+use sudoku::Board;
+use sudoku::RenderOptions;
+use sudoku::Shape;
+
+const SMALL: &str = "1...........3..4";
+
+const PLAIN: &str = "\
+┏━━━┯━━━┳━━━┯━━━┓
+┃ 1 │   ┃   │   ┃
+┠───┼───╂───┼───┨
+┃   │   ┃   │   ┃
+┣━━━┿━━━╋━━━┿━━━┫
+┃   │   ┃   │   ┃
+┠───┼───╂───┼───┨
+┃ 3 │   ┃   │ 4 ┃
+┗━━━┷━━━┻━━━┷━━━┛
+";
+
+const LABELED: &str = "    1   2   3   4
+  ┏━━━┯━━━┳━━━┯━━━┓
+A ┃ 1 │ · ┃ · │ · ┃
+  ┠───┼───╂───┼───┨
+B ┃ · │ · ┃ · │ · ┃
+  ┣━━━┿━━━╋━━━┿━━━┫
+C ┃ · │ · ┃ · │ · ┃
+  ┠───┼───╂───┼───┨
+D ┃ 3 │ · ┃ · │ 4 ┃
+  ┗━━━┷━━━┻━━━┷━━━┛
+";
+
+const CANDIDATES: &str = "\
+┏━━━━━┯━━━━━┳━━━━━┯━━━━━┓
+┃     │   2 ┃   2 │   2 ┃
+┃  1  │ 3 4 ┃ 3 4 │ 3   ┃
+┠─────┼─────╂─────┼─────┨
+┃   2 │   2 ┃ 1 2 │ 1 2 ┃
+┃   4 │ 3 4 ┃ 3 4 │ 3   ┃
+┣━━━━━┿━━━━━╋━━━━━┿━━━━━┫
+┃   2 │ 1 2 ┃ 1 2 │ 1 2 ┃
+┃   4 │   4 ┃ 3   │ 3   ┃
+┠─────┼─────╂─────┼─────┨
+┃     │ 1 2 ┃ 1 2 │     ┃
+┃  3  │     ┃     │  4  ┃
+┗━━━━━┷━━━━━┻━━━━━┷━━━━━┛
+";
+
+fn small() -> Board {
+    let mut b = Board::new();
+    b.read(SMALL).unwrap();
+    b
+}
+
+#[test]
+fn heavy_lines_on_box_edges() {
+    assert_eq!(small().render(&RenderOptions::default()), PLAIN);
+    assert_eq!(Board::new().render(&RenderOptions::default()), "");
+
+    // boxes of 2 lines and 3 columns
+    let mut b = Board::new();
+    b.read_with_shape(&".".repeat(36), Shape::new(2, 3)).unwrap();
+    let text = b.render(&RenderOptions::default());
+    assert_eq!(text.lines().next(), Some("┏━━━┯━━━┯━━━┳━━━┯━━━┯━━━┓"));
+    assert_eq!(text.lines().filter(|l| l.starts_with('┣')).count(), 2);
+    assert_eq!(text.lines().filter(|l| l.starts_with('┠')).count(), 3);
+}
+
+#[test]
+fn labels_and_blanks() {
+    assert_eq!(small().render(&RenderOptions { labels: true, blank: '·', ..RenderOptions::default() }), LABELED);
+}
+
+#[test]
+fn candidates_inside_empty_fields() {
+    assert_eq!(small().render(&RenderOptions { candidates: true, ..RenderOptions::default() }), CANDIDATES);
+}
+
+#[test]
+fn jigsaw_regions_have_heavy_edges() {
+    let b = Board::generate_random_jigsaw(2);
+    let text = b.render(&RenderOptions::default());
+    let regions = b.regions();
+    for (line, row) in text.lines().skip(1).step_by(2).take(4).enumerate() {
+        // every field is followed by its right border
+        let borders: Vec<char> = row.chars().filter(|&c| c == '┃' || c == '│').skip(1).collect();
+        for col in 0..3 {
+            let heavy = regions[line * 4 + col] != regions[line * 4 + col + 1];
+            assert_eq!(borders[col] == '┃', heavy, "{}", text);
+        }
+    }
+}
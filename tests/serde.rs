@@ -0,0 +1,113 @@
+#![cfg(feature = "serde")]
+
+use serde_json::json;
+use serde_json::Value;
+use sudoku::Board;
+use sudoku::Cage;
+use sudoku::Field;
+use sudoku::Origin;
+use sudoku::Shape;
+
+const ESCARGOT: &str = "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..";
+
+fn escargot() -> Board {
+    let mut b = Board::new();
+    b.read(ESCARGOT).unwrap();
+    b
+}
+
+fn assert_round_trip(b: &Board) -> Value {
+    let json = serde_json::to_string(b).unwrap();
+    let c: Board = serde_json::from_str(&json).unwrap();
+    assert_eq!(c.print(false), b.print(false));
+    assert_eq!(c.print_candidates(), b.print_candidates());
+    assert_eq!(c.regions(), b.regions());
+    assert_eq!(c.has_diagonals(), b.has_diagonals());
+    assert_eq!(c.cages(), b.cages());
+    for idx in 0..b.shape().line_size().pow(2) {
+        let (line, col) = (idx / b.shape().line_size(), idx % b.shape().line_size());
+        assert_eq!(c.origin(line, col), b.origin(line, col));
+    }
+    assert_eq!(serde_json::to_string(&c).unwrap(), json);
+    serde_json::from_str(&json).unwrap()
+}
+
+fn error(json: Value) -> String {
+    serde_json::from_value::<Board>(json).err().unwrap().to_string()
+}
+
+#[test]
+fn boards_round_trip() {
+    // givens, entered and derived numbers, and candidates eliminated by a technique
+    let mut b = escargot();
+    assert!(b.enter(0, 1, 7));
+    let step = b.hint().unwrap();
+    assert!(b.apply_step(&step));
+    assert_eq!(b.origin(0, 1), Some(Origin::Entered));
+    let json = assert_round_trip(&b);
+    assert_eq!(json["base"], 3);
+    assert_eq!(json["givens"][0], 1);
+    assert_eq!(json["values"][1], 8);
+    assert_eq!(json["entered"], json!([1]));
+    assert_eq!(json["candidates"][0], json!([]));
+    assert!(json.get("boxes").is_none() && json.get("regions").is_none() && json.get("cages").is_none());
+
+    let json = assert_round_trip(&Board::generate_unique(Shape::new(2, 3)));
+    assert_eq!(json["boxes"], json!([2, 3]));
+    assert!(json.get("base").is_none());
+
+    let json = assert_round_trip(&Board::generate_random_jigsaw(3));
+    assert_eq!(json["regions"].as_array().unwrap().len(), 81);
+    let json = assert_round_trip(&Board::generate_diagonal(2));
+    assert_eq!(json["diagonals"], true);
+    let json = assert_round_trip(&Board::generate_killer(2));
+    assert!(!json["cages"].as_array().unwrap().is_empty());
+    assert_round_trip(&Board::generate(4));
+    assert_round_trip(&Board::new());
+}
+
+#[test]
+fn candidates_may_be_left_out() {
+    let mut json = serde_json::to_value(escargot()).unwrap();
+    json.as_object_mut().unwrap().remove("candidates");
+    let b: Board = serde_json::from_value(json).unwrap();
+    assert_eq!(b.print_candidates(), escargot().print_candidates());
+
+    let empty = vec!(0; 16);
+    let json = json!({"base": 2, "givens": empty, "values": empty, "cages": [{"cells": [0, 1], "sum": 3}]});
+    let b: Board = serde_json::from_value(json).unwrap();
+    assert_eq!(b.cages(), &[Cage::new(vec!(0, 1), 3)]);
+    assert_eq!(b.count_solutions(100), 48);
+}
+
+#[test]
+fn fields_round_trip() {
+    assert_eq!(serde_json::to_string(&Field::Number(4)).unwrap(), "5");
+    assert_eq!(serde_json::to_string(&Field::OptionList([0, 2].into_iter().collect())).unwrap(), "[1,3]");
+    assert!(matches!(serde_json::from_str::<Field>("5").unwrap(), Field::Number(4)));
+    assert!(matches!(serde_json::from_str::<Field>("[1,3]").unwrap(), Field::OptionList(list) if list.len() == 2 && list.contains(2)));
+    assert!(serde_json::from_str::<Field>("0").is_err());
+}
+
+#[test]
+fn invalid_boards_are_rejected() {
+    let board = |base: usize, givens: Vec<usize>| json!({"base": base, "givens": givens, "values": vec!(0; givens.len())});
+    let empty = vec!(0; 16);
+    let mut givens = empty.clone();
+
+    assert!(error(board(2, vec!(0; 15))).contains("givens has 15 fields, expected 16"));
+    assert!(error(json!({"base": 2, "givens": empty, "values": empty, "candidates": [[1]]})).contains("candidates has 1 fields, expected 16"));
+    assert!(error(board(12, vec!(0; 16))).contains("base 12 is not supported"));
+    assert!(error(json!({"boxes": [1, 4], "givens": empty, "values": empty})).contains("base 1 is not supported"));
+    givens[3] = 5;
+    assert!(error(board(2, givens.clone())).contains("value 5 at position 4 is out of range 1-4"));
+    givens[3] = 1;
+    givens[0] = 1;
+    assert!(error(board(2, givens.clone())).contains("value 1 at r1c4 conflicts with r1c1"));
+    givens[3] = 0;
+    assert!(error(json!({"base": 2, "givens": givens, "values": empty, "entered": [0]})).contains("entered field 1 holds no value"));
+    assert!(error(json!({"base": 2, "givens": empty, "values": empty, "entered": [16]})).contains("entered field 17 holds no value"));
+    assert!(error(json!({"base": 2, "givens": givens, "values": givens})).contains("field 1 has both a given and a value"));
+    assert!(error(json!({"base": 2, "givens": empty, "values": empty, "regions": empty})).contains("region 1 has 16 fields, expected 4"));
+    assert!(error(json!({"base": 2, "givens": empty, "values": empty, "cages": [{"cells": [0, 1], "sum": 8}]})).contains("cage 1 of 2 fields can't add up to 8"));
+}
@@ -0,0 +1,81 @@
+use sudoku::Board;
+use sudoku::Candidates;
+use sudoku::Field;
+use sudoku::Shape;
+
+fn numbers(b: &Board) -> Vec<usize> {
+    b.iter().map(|f| match f {
+        Field::Number(n) => *n,
+        Field::OptionList(_) => panic!("unsolved field"),
+    }).collect()
+}
+
+fn assert_valid_solution(b: &Board) {  // every line, column and box holds each number once
+    let shape = b.shape();
+    let line_size = shape.line_size();
+    let numbers = numbers(b);
+    let regions = b.regions();
+    for i in 0..line_size {
+        let line: Candidates = (0..line_size).map(|col| numbers[i * line_size + col]).collect();
+        let col: Candidates = (0..line_size).map(|line| numbers[line * line_size + i]).collect();
+        let quad: Candidates = (0..numbers.len()).filter(|&idx| regions[idx] == i).map(|idx| numbers[idx]).collect();
+        assert_eq!((line, col, quad), (Candidates::full(line_size), Candidates::full(line_size), Candidates::full(line_size)));
+    }
+}
+
+#[test]
+fn standard_shapes_of_line_sizes() {
+    assert_eq!(Shape::for_line_size(6), Some(Shape::new(2, 3)));
+    assert_eq!(Shape::for_line_size(8), Some(Shape::new(2, 4)));
+    assert_eq!(Shape::for_line_size(10), Some(Shape::new(2, 5)));
+    assert_eq!(Shape::for_line_size(12), Some(Shape::new(3, 4)));
+    assert_eq!(Shape::for_line_size(9), Some(Shape::square(3)));
+    assert_eq!(Shape::for_line_size(7), None);
+
+    assert_eq!(Shape::from_name("2x3"), Some(Shape::new(2, 3)));
+    assert_eq!(Shape::from_name("3X4"), Some(Shape::new(3, 4)));
+    assert_eq!(Shape::from_name("3"), Some(Shape::square(3)));
+    assert_eq!(Shape::from_name("1x4"), None);
+    assert_eq!(Shape::from_name("2x"), None);
+    assert_eq!(Shape::new(2, 5).name(), "2x5");
+}
+
+#[test]
+fn boxes_are_rows_by_columns() {
+    let mut b = Board::new();
+    b.read(&".".repeat(36)).unwrap();
+    assert_eq!(b.shape(), Shape::new(2, 3));
+    let regions = b.regions();
+    assert_eq!(&regions[..12], &[0, 0, 0, 1, 1, 1, 0, 0, 0, 1, 1, 1]);
+    assert_eq!(regions[12], 2);
+    assert_eq!(b.quad_iter(1).count(), 6);
+
+    // lengths of two digit boards pick the standard shape unless another one is given
+    for (line_size, shape) in [(8, Shape::new(2, 4)), (10, Shape::new(2, 5)), (12, Shape::new(3, 4))] {
+        let width = if line_size > 9 { 2 } else { 1 };
+        let mut b = Board::new();
+        b.read(&".".repeat(line_size * line_size * width)).unwrap();
+        assert_eq!(b.shape(), shape);
+    }
+    let mut b = Board::new();
+    b.read_with_shape(&"..".repeat(144), Shape::new(2, 6)).unwrap();
+    assert_eq!(b.shape(), Shape::new(2, 6));
+    assert_eq!(&b.regions()[..12], &[0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 1]);
+}
+
+#[test]
+fn rectangular_puzzles_are_generated_and_solved() {
+    for shape in [Shape::new(2, 3), Shape::new(2, 4), Shape::new(2, 5), Shape::new(3, 4)] {
+        let b = Board::generate_unique(shape);
+        assert_eq!(b.shape(), shape);
+        assert!(b.has_unique_solution());
+        let solution = b.clone().solve().unwrap();
+        assert_valid_solution(&solution);
+
+        // the printed puzzle is read back with the same boxes
+        let mut c = Board::new();
+        c.read(b.print(false).trim()).unwrap();
+        assert_eq!(c.shape(), shape);
+        assert_eq!(c.print(true), b.print(true));
+    }
+}
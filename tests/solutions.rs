@@ -0,0 +1,64 @@
+use sudoku::Board;
+use sudoku::Solutions;
+use sudoku::Solver;
+
+const ESCARGOT: &str = "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..";
+
+#[test]
+fn boards_without_fields_have_no_solution() {
+    let b = Board::new();
+    assert_eq!(b.count_solutions(2), 0);
+    assert_eq!(b.count_solutions_with(Solver::DancingLinks, 2), 0);
+    assert!(!b.has_unique_solution());
+    assert!(b.clone().solve().is_none());
+    assert!(b.solve_with(Solver::DancingLinks).is_none());
+}
+
+#[test]
+fn solutions_are_counted_up_to_the_limit() {
+    let mut b = Board::new();
+    b.read(ESCARGOT).unwrap();
+    assert_eq!(b.count_solutions(2), 1);
+    assert!(b.has_unique_solution());
+
+    let mut b = Board::new();
+    b.read(&".".repeat(16)).unwrap();
+    assert_eq!(b.count_solutions(1000), 288);
+    assert_eq!(b.count_solutions(5), 5);
+    assert!(!b.has_unique_solution());
+
+    // the 1s in their boxes leave only the 9 to both open fields of the top row
+    let mut b = Board::new();
+    b.read(&format!(".2345678.1{}1{}", ".".repeat(16), ".".repeat(54))).unwrap();
+    assert_eq!(b.count_solutions(2), 0);
+    assert!(!b.has_unique_solution());
+}
+
+#[test]
+fn solutions_are_distinct_complete_grids() {
+    // near-empty 4x4 boards have many solutions, all of them keep the givens
+    let puzzle = include_str!("../puzzles/puzzles-2.txt").lines().next().unwrap();
+    let mut b = Board::new();
+    b.read(puzzle).unwrap();
+    let solutions: Vec<String> = b.solutions().map(|s| s.print(false).trim().to_string()).collect();
+    assert_eq!(solutions.len(), b.count_solutions(1000));
+    assert!(solutions.len() > 1);
+
+    let mut distinct = solutions.clone();
+    distinct.sort();
+    distinct.dedup();
+    assert_eq!(distinct.len(), solutions.len());
+    for solution in solutions.iter() {
+        assert!(!solution.contains('.'));
+        assert!(puzzle.chars().zip(solution.chars()).all(|(p, s)| p == '.' || p == s));
+        let mut c = Board::new();
+        c.read(solution).unwrap();
+        assert_eq!(c.count_solutions(2), 1);
+    }
+
+    // the iterator is lazy and yields the same grids in the same order every time
+    let first: Vec<String> = b.solutions().take(3).map(|s| s.print(false).trim().to_string()).collect();
+    assert_eq!(first, solutions[..3]);
+    let mut iter = Solutions::new(b.clone());
+    assert_eq!(iter.next().map(|s| s.print(false).trim().to_string()).as_ref(), solutions.first());
+}